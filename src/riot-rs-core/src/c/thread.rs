@@ -244,14 +244,30 @@ pub unsafe extern "C" fn thread_isr_stack_pointer() -> *mut c_void {
     0 as *mut c_void
 }
 
+/// Returns the size of the ISR stack in bytes, or 0 where it isn't known.
 #[no_mangle]
 pub unsafe extern "C" fn thread_isr_stack_size() -> usize {
-    0
+    #[cfg(context = "cortex-m")]
+    {
+        riot_rs_rt::isr_stack_size()
+    }
+    #[cfg(not(context = "cortex-m"))]
+    {
+        0
+    }
 }
 
+/// Returns the highest ISR stack usage since startup in bytes, or 0 where it isn't measured.
 #[no_mangle]
 pub unsafe extern "C" fn thread_isr_stack_usage() -> usize {
-    0
+    #[cfg(context = "cortex-m")]
+    {
+        riot_rs_rt::isr_stack_usage()
+    }
+    #[cfg(not(context = "cortex-m"))]
+    {
+        0
+    }
 }
 
 #[no_mangle]
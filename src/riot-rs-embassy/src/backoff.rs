@@ -0,0 +1,121 @@
+//! Retries fallible operations with exponential backoff.
+//!
+//! [`Backoff`] computes the delays between attempts, [`retry()`] (and [`retry_blocking()`] for
+//! threads) apply it to an operation.
+//!
+//! When the `hwrng` feature is enabled, delays are randomized ("equal jitter": between half and
+//! all of the exponential delay), so that devices failing at the same time, e.g., because of a
+//! common server outage, don't retry in lockstep.
+
+use core::future::Future;
+
+use embassy_time::{Duration, Timer};
+
+/// An exponential backoff policy.
+#[derive(Clone, Debug)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+    max_attempts: Option<u32>,
+    attempt: u32,
+}
+
+impl Backoff {
+    /// Creates a new [`Backoff`] whose delay starts at `initial` and doubles after every attempt,
+    /// up to `max`.
+    ///
+    /// By default, the number of attempts is unbounded.
+    pub const fn new(initial: Duration, max: Duration) -> Self {
+        Self {
+            initial,
+            max,
+            max_attempts: None,
+            attempt: 0,
+        }
+    }
+
+    /// Limits the number of retries, i.e., attempts after the first one, to `max_attempts`.
+    #[must_use]
+    pub const fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Starts over with the initial delay, e.g., after a connection has been successfully
+    /// established.
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+
+    /// Returns the delay to wait before the next attempt, or `None` if attempts are exhausted.
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        if self.max_attempts.is_some_and(|max| self.attempt >= max) {
+            return None;
+        }
+
+        let factor = 1u64.checked_shl(self.attempt).unwrap_or(u64::MAX);
+        let delay = self
+            .initial
+            .as_ticks()
+            .saturating_mul(factor)
+            .min(self.max.as_ticks());
+        self.attempt = self.attempt.saturating_add(1);
+
+        Some(Duration::from_ticks(jitter(delay)))
+    }
+}
+
+#[cfg(feature = "hwrng")]
+fn jitter(delay: u64) -> u64 {
+    let half = delay / 2;
+    half + u64::from(riot_rs_random::fast_u32()) % (delay - half + 1)
+}
+
+#[cfg(not(feature = "hwrng"))]
+fn jitter(delay: u64) -> u64 {
+    delay
+}
+
+/// Calls `f` until it succeeds, waiting according to `backoff` between attempts.
+///
+/// # Errors
+///
+/// Returns the last error once `backoff` is exhausted.
+pub async fn retry<T, E, F, Fut>(backoff: &mut Backoff, mut f: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(err) => match backoff.next_delay() {
+                Some(delay) => Timer::after(delay).await,
+                None => return Err(err),
+            },
+        }
+    }
+}
+
+/// Calls `f` until it succeeds, sleeping according to `backoff` between attempts.
+///
+/// **NOTE**: must not be called outside thread context!
+///
+/// # Errors
+///
+/// Returns the last error once `backoff` is exhausted.
+#[cfg(feature = "threading")]
+pub fn retry_blocking<T, E>(
+    backoff: &mut Backoff,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(err) => match backoff.next_delay() {
+                Some(delay) => crate::blocker::sleep(delay),
+                None => return Err(err),
+            },
+        }
+    }
+}
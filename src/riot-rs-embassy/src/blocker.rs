@@ -3,6 +3,9 @@ use core::pin::Pin;
 use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
 use riot_rs_threads::{current_pid, flags, flags::ThreadFlags, ThreadId};
 
+#[cfg(feature = "time")]
+use embassy_time::{Duration, Instant};
+
 const THREAD_FLAG_WAKER: ThreadFlags = 1; // TODO: find more appropriate value
 
 fn wake(ptr: *const ()) {
@@ -34,3 +37,82 @@ pub fn block_on<F: Future>(mut fut: F) -> F::Output {
         flags::wait_any(THREAD_FLAG_WAKER);
     }
 }
+
+/// Puts the current thread to sleep for `duration`.
+///
+/// The thread is woken by the system's embassy timer, so this is as precise as
+/// `embassy_time::Timer` and uses the same clock.
+#[cfg(feature = "time")]
+pub fn sleep(duration: Duration) {
+    sleep_until(Instant::now() + duration)
+}
+
+/// Puts the current thread to sleep until `deadline`.
+#[cfg(feature = "time")]
+pub fn sleep_until(deadline: Instant) {
+    let thread_id = current_pid().unwrap();
+    sleep_timer::DEADLINES.lock(|deadlines| {
+        deadlines.borrow_mut()[usize::from(thread_id)] = Some(deadline);
+    });
+    sleep_timer::DEADLINES_CHANGED.signal(());
+
+    // Waking up early, e.g., from a stale waker of `block_on()`, is harmless.
+    while Instant::now() < deadline {
+        flags::wait_any(THREAD_FLAG_WAKER);
+    }
+}
+
+#[cfg(feature = "time")]
+pub(crate) mod sleep_timer {
+    //! Wakes sleeping threads from within the embassy executor.
+    //!
+    //! `embassy_time::Timer` can only be awaited by executor tasks, so threads
+    //! register their deadline here and a task wakes them.
+
+    use core::cell::RefCell;
+
+    use embassy_sync::{
+        blocking_mutex::{raw::CriticalSectionRawMutex, CriticalSectionMutex},
+        signal::Signal,
+    };
+    use embassy_time::{with_timeout, Instant};
+    use riot_rs_threads::{flags, ThreadId, THREADS_NUMOF};
+
+    use super::THREAD_FLAG_WAKER;
+
+    /// Wake-up time of each thread, by [`ThreadId`].
+    pub(super) static DEADLINES: CriticalSectionMutex<RefCell<[Option<Instant>; THREADS_NUMOF]>> =
+        CriticalSectionMutex::new(RefCell::new([None; THREADS_NUMOF]));
+
+    /// Signals [`sleep_timer_task()`] that a deadline was added.
+    pub(super) static DEADLINES_CHANGED: Signal<CriticalSectionRawMutex, ()> = Signal::new();
+
+    #[embassy_executor::task]
+    pub(crate) async fn sleep_timer_task() -> ! {
+        loop {
+            let now = Instant::now();
+            let next = DEADLINES.lock(|deadlines| {
+                let mut next: Option<Instant> = None;
+                for (i, deadline) in deadlines.borrow_mut().iter_mut().enumerate() {
+                    match *deadline {
+                        Some(at) if at <= now => {
+                            flags::set(ThreadId::new(i as u8), THREAD_FLAG_WAKER);
+                            *deadline = None;
+                        }
+                        Some(at) => next = Some(next.map_or(at, |next| next.min(at))),
+                        None => {}
+                    }
+                }
+                next
+            });
+
+            match next {
+                Some(next) => {
+                    let timeout = next.saturating_duration_since(Instant::now());
+                    let _ = with_timeout(timeout, DEADLINES_CHANGED.wait()).await;
+                }
+                None => DEADLINES_CHANGED.wait().await,
+            }
+        }
+    }
+}
@@ -61,9 +61,13 @@ use wifi::NetworkDevice;
 #[cfg(feature = "net")]
 pub use network::NetworkStack;
 
+#[cfg(feature = "time")]
+pub mod backoff;
 #[cfg(feature = "threading")]
 pub mod blocker;
 pub mod delegate;
+#[cfg(feature = "time")]
+pub mod rate_limiter;
 pub mod sendcell;
 
 pub type Task = fn(Spawner, &mut arch::OptionalPeripherals);
@@ -123,6 +127,9 @@ async fn init_task(mut peripherals: arch::OptionalPeripherals) {
 
     let spawner = Spawner::for_current_executor().await;
 
+    #[cfg(all(feature = "threading", feature = "time"))]
+    spawner.must_spawn(blocker::sleep_timer::sleep_timer_task());
+
     for task in EMBASSY_TASKS {
         task(spawner, &mut peripherals);
     }
@@ -206,6 +213,8 @@ async fn init_task(mut peripherals: arch::OptionalPeripherals) {
             4,
             "maximum number of concurrent sockets allowed by the network stack"
         );
+        // The DNS socket is not counted against the sockets available to applications.
+        const SOCKETS: usize = MAX_CONCURRENT_SOCKETS + cfg!(feature = "dns") as usize;
 
         let config = network::config();
 
@@ -220,7 +229,7 @@ async fn init_task(mut peripherals: arch::OptionalPeripherals) {
         let stack = &*make_static!(Stack::new(
             device,
             config,
-            make_static!(StackResources::<MAX_CONCURRENT_SOCKETS>::new()),
+            make_static!(StackResources::<SOCKETS>::new()),
             seed
         ));
 
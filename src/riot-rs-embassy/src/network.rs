@@ -1,5 +1,8 @@
 //! To provide a custom network configuration, use the `riot_rs::config` attribute macro.
 
+#[cfg(feature = "dns")]
+pub mod dns;
+
 use core::cell::OnceCell;
 
 use embassy_executor::Spawner;
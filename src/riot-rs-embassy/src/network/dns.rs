@@ -0,0 +1,113 @@
+//! Provides DNS name resolution with a small cache.
+//!
+//! Queries are sent to the DNS servers of the network configuration: the ones provided by DHCP,
+//! or the `dns_servers` of a static configuration (see `riot_rs::config`).
+//!
+//! Only A records are queried, so names resolve to IPv4 addresses only.
+//!
+//! The DNS socket takes one of the network stack's socket slots; with this feature enabled, one
+//! slot is added on top of `CONFIG_NETWORK_MAX_CONCURRENT_SOCKETS`.
+
+use core::cell::RefCell;
+
+use embassy_net::{dns::DnsQueryType, IpAddress};
+use embassy_sync::blocking_mutex::CriticalSectionMutex;
+use embassy_time::{Duration, Instant};
+
+pub use embassy_net::dns::Error;
+
+/// Number of names cached.
+const CACHE_SIZE: usize = 4;
+/// Longest name that is cached, longer names are resolved anew every time.
+const MAX_CACHED_NAME_LEN: usize = 64;
+/// How long successful resolutions are cached.
+const POSITIVE_TTL: Duration = Duration::from_secs(5 * 60);
+/// How long failed resolutions are cached.
+const NEGATIVE_TTL: Duration = Duration::from_secs(30);
+
+struct CacheEntry {
+    name: heapless::String<MAX_CACHED_NAME_LEN>,
+    /// `None` for a failed resolution.
+    addr: Option<IpAddress>,
+    expires: Instant,
+}
+
+static CACHE: CriticalSectionMutex<RefCell<heapless::Vec<CacheEntry, CACHE_SIZE>>> =
+    CriticalSectionMutex::new(RefCell::new(heapless::Vec::new()));
+
+/// Resolves `name` to an IPv4 address, by querying its A records.
+///
+/// Results, including failures, are cached for a few minutes and seconds, respectively.
+/// Failures are only cached if DNS servers were configured to answer the query: before that,
+/// e.g., while DHCP is still in progress, the next attempt may well succeed.
+///
+/// Returns [`Error::Failed`] if the network stack isn't initialized, no DNS servers are configured
+/// or the name could not be resolved.
+pub async fn resolve(name: &str) -> Result<IpAddress, Error> {
+    if let Some(cached) = lookup(name) {
+        return cached.ok_or(Error::Failed);
+    }
+
+    let stack = super::network_stack().await.ok_or(Error::Failed)?;
+    let has_servers = stack
+        .config_v4()
+        .is_some_and(|config| !config.dns_servers.is_empty());
+    let addr = match stack.dns_query(name, DnsQueryType::A).await {
+        Ok(addrs) => addrs.first().copied(),
+        // The servers did not resolve the name. This also covers all of them timing out, which
+        // `embassy_net` does not tell apart.
+        Err(Error::Failed) if has_servers => None,
+        // Without servers the query cannot have been answered, don't cache that.
+        Err(Error::Failed) => return Err(Error::Failed),
+        // Not worth caching, the name itself is invalid.
+        Err(err) => return Err(err),
+    };
+
+    insert(name, addr);
+
+    addr.ok_or(Error::Failed)
+}
+
+/// Drops all cached resolutions, e.g., after a network change.
+pub fn clear_cache() {
+    CACHE.lock(|cache| cache.borrow_mut().clear());
+}
+
+/// Returns the cached resolution of `name`, if any.
+fn lookup(name: &str) -> Option<Option<IpAddress>> {
+    let now = Instant::now();
+    CACHE.lock(|cache| {
+        cache
+            .borrow()
+            .iter()
+            .find(|entry| entry.name == name && entry.expires > now)
+            .map(|entry| entry.addr)
+    })
+}
+
+/// Caches the resolution of `name`, replacing the entry closest to expiry if the cache is full.
+fn insert(name: &str, addr: Option<IpAddress>) {
+    let Ok(name) = heapless::String::try_from(name) else {
+        return;
+    };
+    let ttl = if addr.is_some() {
+        POSITIVE_TTL
+    } else {
+        NEGATIVE_TTL
+    };
+    let entry = CacheEntry {
+        name,
+        addr,
+        expires: Instant::now() + ttl,
+    };
+
+    CACHE.lock(|cache| {
+        let mut cache = cache.borrow_mut();
+        cache.retain(|cached| cached.name != entry.name);
+        if let Err(entry) = cache.push(entry) {
+            if let Some(oldest) = cache.iter_mut().min_by_key(|cached| cached.expires) {
+                *oldest = entry;
+            }
+        }
+    });
+}
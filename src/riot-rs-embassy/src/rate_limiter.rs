@@ -0,0 +1,66 @@
+//! Bounds the rate of events.
+//!
+//! This module provides [`RateLimiter`], a token bucket that can be shared between threads, tasks
+//! and ISRs, e.g., to bound the rate of log messages or of packets sent.
+
+use core::cell::Cell;
+
+use critical_section::Mutex;
+use embassy_time::{Duration, Instant, Timer};
+
+/// A token bucket allowing one event per `interval` on average, with bursts of up to `burst`
+/// events.
+///
+/// This is implemented as a Generic Cell Rate Algorithm, which only needs to store a single
+/// timestamp.
+pub struct RateLimiter {
+    interval: Duration,
+    /// How far ahead of now the theoretical arrival time may be for an event to be allowed.
+    tolerance: Duration,
+    /// Theoretical arrival time of the next event, in ticks.
+    tat: Mutex<Cell<u64>>,
+}
+
+impl RateLimiter {
+    /// Creates a new [`RateLimiter`], with a full bucket.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is 0.
+    pub const fn new(interval: Duration, burst: u32) -> Self {
+        assert!(burst > 0, "burst must be at least 1");
+        Self {
+            interval,
+            tolerance: Duration::from_ticks(interval.as_ticks() * (burst as u64 - 1)),
+            tat: Mutex::new(Cell::new(0)),
+        }
+    }
+
+    /// Takes a token if one is available.
+    ///
+    /// Returns `false` if the event should be dropped or deferred.
+    pub fn try_acquire(&self) -> bool {
+        self.acquire_at(Instant::now()).is_ok()
+    }
+
+    /// Waits until a token is available and takes it.
+    pub async fn acquire(&self) {
+        while let Err(available_at) = self.acquire_at(Instant::now()) {
+            Timer::at(available_at).await;
+        }
+    }
+
+    /// Takes a token if one is available at `now`, otherwise returns when the next one will be.
+    fn acquire_at(&self, now: Instant) -> Result<(), Instant> {
+        critical_section::with(|cs| {
+            let tat = self.tat.borrow(cs);
+            let next = Instant::from_ticks(tat.get()).max(now);
+            let available_at = next.checked_sub(self.tolerance).unwrap_or(Instant::MIN);
+            if available_at > now {
+                return Err(available_at);
+            }
+            tat.set((next + self.interval).as_ticks());
+            Ok(())
+        })
+    }
+}
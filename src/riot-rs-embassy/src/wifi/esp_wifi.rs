@@ -1,5 +1,5 @@
 use esp_wifi::{wifi::WifiStaDevice, EspWifiInitialization};
-use once_cell::sync::OnceCell;
+use riot_rs_utils::os_cell::OsCell;
 
 use crate::{arch::OptionalPeripherals, Spawner};
 
@@ -10,13 +10,11 @@ pub type NetworkDevice = WifiDevice<'static, WifiStaDevice>;
 // Ideally, all Wi-Fi initialization would happen here.
 // Unfortunately that's complicated, so we're using WIFI_INIT to pass the
 // `EspWifiInitialization` from `crate::arch::esp::init()`.
-// Using a `once_cell::OnceCell` here for critical-section support, just to be
-// sure.
-pub static WIFI_INIT: OnceCell<EspWifiInitialization> = OnceCell::new();
+pub static WIFI_INIT: OsCell<EspWifiInitialization> = OsCell::new();
 
 pub fn init(peripherals: &mut OptionalPeripherals, spawner: Spawner) -> NetworkDevice {
     let wifi = peripherals.WIFI.take().unwrap();
-    let init = WIFI_INIT.get().unwrap();
+    let init = WIFI_INIT.get_initialized();
     let (device, controller) = esp_wifi::wifi::new_with_mode(init, wifi, WifiStaDevice).unwrap();
 
     spawner.spawn(connection(controller)).ok();
@@ -5,6 +5,7 @@ mod utils;
 use proc_macro::TokenStream;
 
 include!("config.rs");
+include!("ramfunc.rs");
 include!("spawner.rs");
 include!("task.rs");
 include!("thread.rs");
@@ -0,0 +1,45 @@
+/// Places the function this attribute macro is applied on in RAM instead of flash.
+///
+/// This is needed for functions that must keep running while the flash is unavailable (e.g.,
+/// while it is being written to), and benefits latency-critical functions on chips executing in
+/// place from external flash.
+///
+/// The function is copied to RAM together with initialized static data, during startup.
+/// It is never inlined, as inlining it into a caller located in flash would defeat its purpose.
+/// Functions it calls are *not* moved to RAM.
+///
+/// On architectures without support for this, the function is left in flash.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[riot_rs::ramfunc]
+/// fn busy_wait_for_flash() {
+///     // ...
+/// }
+/// ```
+///
+/// # Panics
+///
+/// This macro panics when parameters are passed to it.
+#[proc_macro_attribute]
+pub fn ramfunc(args: TokenStream, item: TokenStream) -> TokenStream {
+    use quote::quote;
+
+    assert!(args.is_empty(), "`ramfunc` does not take any parameters");
+
+    let function = syn::parse_macro_input!(item as syn::ItemFn);
+
+    // Functions in `.data` are copied to RAM by `cortex-m-rt`, those in `.rwtext` by the ESP
+    // runtime.
+    let section = format!(".data.ramfunc.{}", function.sig.ident);
+
+    let expanded = quote! {
+        #[cfg_attr(context = "cortex-m", link_section = #section)]
+        #[cfg_attr(context = "esp", link_section = ".rwtext")]
+        #[inline(never)]
+        #function
+    };
+
+    TokenStream::from(expanded)
+}
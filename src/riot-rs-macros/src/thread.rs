@@ -6,7 +6,8 @@
 ///
 /// - `autostart`: (*mandatory*) autostart the thread.
 /// - `stacksize`: (*optional*) the size of the stack allocated to the thread (in bytes).
-/// - `priority`: (*optional*) the thread's priority.
+/// - `priority`: (*optional*) the thread's priority, either a number or the name of one of the
+///   bands in `riot_rs::thread::priority`, e.g., `NETWORK`.
 ///
 /// # Examples
 ///
@@ -28,6 +29,13 @@
 /// }
 /// ```
 ///
+/// This starts a thread with the priority of the network band:
+///
+/// ```ignore
+/// #[riot_rs::thread(autostart, priority = NETWORK)]
+/// fn network_worker() {}
+/// ```
+///
 /// # Panics
 ///
 /// This macro panics when the `riot-rs` crate cannot be found as a dependency of the crate where
@@ -72,6 +80,11 @@ pub fn thread(args: TokenStream, item: TokenStream) -> TokenStream {
         }
     };
 
+    let priority = match priority {
+        Priority::Level(level) => quote! { #level },
+        Priority::Named(name) => quote! { #thread_crate::priority::#name },
+    };
+
     let expanded = quote! {
         #no_mangle_attr
         #thread_function
@@ -85,7 +98,13 @@ pub fn thread(args: TokenStream, item: TokenStream) -> TokenStream {
 mod thread {
     pub struct Parameters {
         pub stack_size: u64,
-        pub priority: u8,
+        pub priority: Priority,
+    }
+
+    pub enum Priority {
+        Level(u8),
+        /// One of the named priority bands.
+        Named(syn::Ident),
     }
 
     impl Default for Parameters {
@@ -93,7 +112,7 @@ mod thread {
             // TODO: proper values
             Self {
                 stack_size: 2048,
-                priority: 1,
+                priority: Priority::Level(1),
             }
         }
     }
@@ -106,9 +125,13 @@ mod thread {
                 parse_base10_or_panic(&l, "stack_size")
             });
 
-            let priority = attrs
-                .priority
-                .map_or(default.priority, |l| parse_base10_or_panic(&l, "priority"));
+            let priority = match attrs.priority {
+                Some(PriorityAttr::Level(l)) => {
+                    Priority::Level(parse_base10_or_panic(&l, "priority"))
+                }
+                Some(PriorityAttr::Named(name)) => Priority::Named(name),
+                None => default.priority,
+            };
 
             Self {
                 stack_size,
@@ -142,10 +165,15 @@ mod thread {
     pub struct Attributes {
         pub autostart: bool,
         pub stack_size: Option<syn::LitInt>,
-        pub priority: Option<syn::LitInt>,
+        pub priority: Option<PriorityAttr>,
         pub no_mangle: bool,
     }
 
+    pub enum PriorityAttr {
+        Level(syn::LitInt),
+        Named(syn::Ident),
+    }
+
     impl Attributes {
         /// Parse macro attributes.
         ///
@@ -164,7 +192,12 @@ mod thread {
             }
 
             if meta.path.is_ident("priority") {
-                self.priority = Some(meta.value()?.parse()?);
+                let value = meta.value()?;
+                self.priority = Some(if value.peek(syn::Ident) {
+                    PriorityAttr::Named(value.parse()?)
+                } else {
+                    PriorityAttr::Level(value.parse()?)
+                });
                 return Ok(());
             }
 
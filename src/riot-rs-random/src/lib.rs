@@ -3,6 +3,8 @@
 //! The module provides functions for use by applications, [`fast_rng()`] and [`crypto_rng()`],
 //! which produce owned types that provide the [`rand_core::RngCore`] and
 //! [`rand_core::CryptoRng`] traits, respectively.
+//! For code that only needs the occasional random number, e.g., for backoff or jitter,
+//! [`fast_u32()`] provides one without locking, including from interrupt handlers.
 //!
 //! The crate abstracts over multiple aspects of RNGs:
 //! * Where do we take a valid seed for the RNG from?
@@ -24,6 +26,7 @@
 //! [`FastRng`] or [`CryptoRng`] is guaranteed.
 #![no_std]
 
+use portable_atomic::{AtomicU32, Ordering};
 use rand_core::{RngCore, SeedableRng};
 
 /// A global RNG.
@@ -33,6 +36,11 @@ static RNG: embassy_sync::blocking_mutex::Mutex<
     core::cell::RefCell<Option<SelectedRng>>,
 > = embassy_sync::blocking_mutex::Mutex::new(core::cell::RefCell::new(None));
 
+/// State of the xorshift generator behind [`fast_u32()`].
+///
+/// Zero until initialized, as zero is the one state xorshift can't leave.
+static FAST_STATE: AtomicU32 = AtomicU32::new(0);
+
 /// Type of the global RNG when needing the ability to produce cryptographically secure random
 /// numbers.
 ///
@@ -138,6 +146,14 @@ pub fn construct_rng(hwrng: impl RngCore) {
             SelectedRng::from_rng(hwrng).expect("Hardware RNG failed to provide entropy"),
         ))
     });
+
+    let fast_seed = with_global(|i| loop {
+        let seed = i.next_u32();
+        if seed != 0 {
+            break seed;
+        }
+    });
+    FAST_STATE.store(fast_seed, Ordering::Relaxed);
 }
 
 /// Returns a suitably initialized fast random number generator.
@@ -149,6 +165,33 @@ pub fn fast_rng() -> FastRng {
     }
 }
 
+/// Returns a random `u32` from a global, lock-free generator.
+///
+/// This is cheap and can be called from any context, including interrupt handlers. The
+/// generator is a xorshift generator seeded from the global RNG: its output is neither
+/// cryptographically secure nor of high statistical quality, which is fine for, e.g., backoff
+/// and jitter. Prefer [`fast_rng()`] or [`crypto_rng()`] for anything else.
+///
+/// # Panics
+///
+/// … if initialization did not happen.
+pub fn fast_u32() -> u32 {
+    let state = FAST_STATE
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |state| {
+            Some(xorshift32(state))
+        })
+        .unwrap();
+    assert!(state != 0, "Initialization should have populated RNG");
+    xorshift32(state)
+}
+
+fn xorshift32(mut x: u32) -> u32 {
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    x
+}
+
 /// Returns a suitably initialized cryptographically secure random number generator.
 #[inline]
 #[cfg(feature = "csprng")]
@@ -157,3 +200,24 @@ pub fn crypto_rng() -> CryptoRng {
         _private: Default::default(),
     }
 }
+
+/// Returns a random (version 4) UUID, in its big-endian binary representation.
+#[cfg(feature = "csprng")]
+pub fn uuid_v4() -> [u8; 16] {
+    let mut uuid: [u8; 16] = nonce();
+    // Set the version (4) and variant (RFC 4122) bits, see RFC 4122, section 4.4.
+    uuid[6] = (uuid[6] & 0x0f) | 0x40;
+    uuid[8] = (uuid[8] & 0x3f) | 0x80;
+    uuid
+}
+
+/// Returns `N` cryptographically secure random bytes, e.g., for use as a nonce.
+///
+/// Randomly generated nonces are only unlikely to repeat if `N` is large enough for the number of
+/// nonces generated with the same key; with AEAD algorithms using short nonces, prefer counters.
+#[cfg(feature = "csprng")]
+pub fn nonce<const N: usize>() -> [u8; N] {
+    let mut nonce = [0; N];
+    crypto_rng().fill_bytes(&mut nonce);
+    nonce
+}
@@ -16,5 +16,53 @@ fn main() {
         std::fs::copy("linkme-esp32c6-fixup.x", out.join("linkme-esp-fixup.x")).unwrap();
     }
 
+    write_budget_script(&out.join("budget.x"));
+
     println!("cargo:rustc-link-search={}", out.display());
 }
+
+/// Writes a linker script failing the link if the image exceeds a memory budget, configured
+/// through the following environment variables (in bytes):
+///
+/// - `CONFIG_BUDGET_RAM`: all statically allocated RAM, including the ISR stack,
+/// - `CONFIG_BUDGET_FLASH`: the whole flash image, i.e., everything up to the end of the initial
+///   values of `.data`, including the `linkme` sections placed after `.rodata`,
+/// - `CONFIG_BUDGET_BSS`: zero-initialized statics only,
+/// - `CONFIG_BUDGET_ISR_STACK`: the ISR stack only.
+///
+/// Section names are those of `cortex-m-rt`.
+fn write_budget_script(path: &std::path::Path) {
+    let budgets = [
+        (
+            "CONFIG_BUDGET_RAM",
+            "SIZEOF(.data) + SIZEOF(.bss) + SIZEOF(.uninit) + SIZEOF(.isr_stack)",
+            "RAM",
+        ),
+        (
+            "CONFIG_BUDGET_FLASH",
+            // Address based, so that sections that got discarded because they are empty (e.g.,
+            // unused `linkme` slices) don't need to be special-cased.
+            "LOADADDR(.data) + SIZEOF(.data) - ORIGIN(FLASH)",
+            "flash",
+        ),
+        ("CONFIG_BUDGET_BSS", "SIZEOF(.bss)", ".bss"),
+        ("CONFIG_BUDGET_ISR_STACK", "SIZEOF(.isr_stack)", "ISR stack"),
+    ];
+
+    let mut script = String::new();
+    for (var, usage, memory) in budgets {
+        println!("cargo:rerun-if-env-changed={var}");
+        let Some(budget) = env::var_os(var) else {
+            continue;
+        };
+        let budget: usize = budget
+            .to_str()
+            .and_then(|budget| budget.parse().ok())
+            .unwrap_or_else(|| panic!("{var} must be a number of bytes"));
+        script.push_str(&format!(
+            "ASSERT({usage} <= {budget}, \"ERROR(riot-rs-rt): {memory} usage exceeds {var}={budget}\");\n"
+        ));
+    }
+
+    std::fs::write(path, script).unwrap();
+}
@@ -1,6 +1,10 @@
 SECTIONS {
+  linkme_EARLY_INIT_FUNCS : { *(linkme_EARLY_INIT_FUNCS) } > FLASH
+  linkm2_EARLY_INIT_FUNCS : { *(linkm2_EARLY_INIT_FUNCS) } > FLASH
   linkme_INIT_FUNCS : { *(linkme_INIT_FUNCS) } > FLASH
   linkm2_INIT_FUNCS : { *(linkm2_INIT_FUNCS) } > FLASH
+  linkme_SHUTDOWN_FUNCS : { *(linkme_SHUTDOWN_FUNCS) } > FLASH
+  linkm2_SHUTDOWN_FUNCS : { *(linkm2_SHUTDOWN_FUNCS) } > FLASH
   linkme_EMBASSY_TASKS : { *(linkme_EMBASSY_TASKS) } > FLASH
   linkm2_EMBASSY_TASKS : { *(linkm2_EMBASSY_TASKS) } > FLASH
   linkm2_USB_BUILDER_HOOKS : { *(linkm2_USB_BUILDER_HOOKS) } > FLASH
@@ -188,6 +188,8 @@ fn main() -> ! {
 }
 
 pub fn init() {
+    paint_isr_stack();
+
     // First, configure vector table address.
     // This is necessary when the vector table is not at its default position,
     // e.g., when there's a bootloader the default address.
@@ -208,3 +210,123 @@ pub fn init() {
         p.SCB.set_priority(SystemHandler::PendSV, 0xFF);
     }
 }
+
+extern "C" {
+    /// Lowest address of the ISR stack, see `isr_stack.ld.in`.
+    static _stack_bottom: u32;
+    /// Highest address of the ISR stack, see `isr_stack.ld.in`.
+    static _stack_start: u32;
+}
+
+/// Pattern the unused part of the ISR stack is filled with, see [`isr_stack_usage()`].
+const ISR_STACK_PAINT: u32 = 0xCCCC_CCCC;
+
+/// Bytes right below the stack pointer that [`paint_isr_stack()`] leaves untouched.
+///
+/// In unoptimized builds, the `write_volatile()` and `add()` calls in its loop are not inlined, so
+/// their frames live right below the stack pointer while it paints. They need a few words each;
+/// this leaves room for them with plenty to spare. The margin is reported as used by
+/// [`isr_stack_usage()`], on top of the startup frames above it.
+const ISR_STACK_PAINT_MARGIN: usize = 64;
+
+fn isr_stack_bounds() -> (*mut u32, *mut u32) {
+    // SAFETY: only the addresses of the linker symbols are used.
+    unsafe {
+        (
+            core::ptr::addr_of!(_stack_bottom) as *mut u32,
+            core::ptr::addr_of!(_stack_start) as *mut u32,
+        )
+    }
+}
+
+/// Fills the ISR stack below the current stack pointer with [`ISR_STACK_PAINT`].
+///
+/// Must be called while running on the ISR stack, before interrupts get enabled.
+#[inline(never)]
+fn paint_isr_stack() {
+    let (bottom, _) = isr_stack_bounds();
+    let end = (cortex_m::register::msp::read() as usize - ISR_STACK_PAINT_MARGIN) as *mut u32;
+    let mut word = bottom;
+    while word < end {
+        // SAFETY: `word` is within the ISR stack, below any frame in use.
+        unsafe {
+            word.write_volatile(ISR_STACK_PAINT);
+            word = word.add(1);
+        }
+    }
+}
+
+/// Returns the ISR stack size in bytes.
+pub fn isr_stack_size() -> usize {
+    let (bottom, top) = isr_stack_bounds();
+    top as usize - bottom as usize
+}
+
+/// Returns the maximum ISR stack usage so far in bytes.
+///
+/// This is the part of the stack that was ever overwritten after startup, so it may
+/// underestimate usage if a frame happens to contain [`ISR_STACK_PAINT`].
+pub fn isr_stack_usage() -> usize {
+    let (bottom, top) = isr_stack_bounds();
+    let mut word = bottom;
+    // SAFETY: `word` stays within the ISR stack.
+    while word < top && unsafe { word.read_volatile() } == ISR_STACK_PAINT {
+        word = unsafe { word.add(1) };
+    }
+    top as usize - word as usize
+}
+
+/// Requests a system reset.
+pub fn reset() -> ! {
+    cortex_m::peripheral::SCB::sys_reset()
+}
+
+/// Resets into the USB bootloader.
+///
+/// Sets `GPREGRET` to the magic value the UF2 bootloader found on most nRF52840 USB boards checks
+/// for, then resets.
+#[cfg(context = "nrf52")]
+pub fn reset_to_bootloader() -> ! {
+    /// `POWER.GPREGRET`, retained across soft resets.
+    const GPREGRET: *mut u32 = 0x4000_051C as *mut u32;
+    const DFU_MAGIC_UF2_RESET: u32 = 0x57;
+
+    // SAFETY: `GPREGRET` is a general purpose retention register, only read by the bootloader.
+    unsafe { core::ptr::write_volatile(GPREGRET, DFU_MAGIC_UF2_RESET) };
+    reset()
+}
+
+/// Resets into the USB bootloader (BOOTSEL mode) of the RP2040 boot ROM.
+#[cfg(context = "rp2040")]
+pub fn reset_to_bootloader() -> ! {
+    /// Pointer to the ROM function table.
+    const ROM_FUNC_TABLE: *const u16 = 0x14 as *const u16;
+    /// Pointer to the ROM table lookup function.
+    const ROM_TABLE_LOOKUP: *const u16 = 0x18 as *const u16;
+    /// Code of `reset_to_usb_boot()` in the ROM function table.
+    const RESET_TO_USB_BOOT: u32 = u32::from_le_bytes([b'U', b'B', 0, 0]);
+
+    type RomTableLookup = unsafe extern "C" fn(table: *const u16, code: u32) -> usize;
+    type ResetToUsbBoot =
+        unsafe extern "C" fn(gpio_activity_pin_mask: u32, disable_interface_mask: u32) -> !;
+
+    // SAFETY: the boot ROM is always mapped at address 0 and provides these pointers and
+    // functions, as documented in section 2.8.3 of the RP2040 datasheet.
+    unsafe {
+        let table = usize::from(core::ptr::read_volatile(ROM_FUNC_TABLE)) as *const u16;
+        let lookup: RomTableLookup =
+            core::mem::transmute(usize::from(core::ptr::read_volatile(ROM_TABLE_LOOKUP)));
+        let reset_to_usb_boot: ResetToUsbBoot =
+            core::mem::transmute(lookup(table, RESET_TO_USB_BOOT));
+        // No activity LED, enable both the mass storage and the PICOBOOT interfaces.
+        reset_to_usb_boot(0, 0)
+    }
+}
+
+/// Disables interrupts and halts the CPU.
+pub fn halt() -> ! {
+    cortex_m::interrupt::disable();
+    loop {
+        cortex_m::asm::wfi();
+    }
+}
@@ -4,3 +4,19 @@ fn main() -> ! {
 }
 
 pub fn init() {}
+
+/// Requests a software reset of the chip.
+pub fn reset() -> ! {
+    esp_hal::reset::software_reset();
+    #[allow(clippy::empty_loop)]
+    loop {}
+}
+
+/// Disables interrupts and halts the CPU.
+pub fn halt() -> ! {
+    // SAFETY: interrupts are never re-enabled.
+    unsafe { esp_hal::riscv::interrupt::disable() };
+    loop {
+        esp_hal::riscv::asm::wfi();
+    }
+}
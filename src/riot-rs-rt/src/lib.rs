@@ -11,10 +11,13 @@
 #![feature(custom_test_frameworks)]
 #![test_runner(crate::testing::test_runner)]
 #![reexport_test_harness_main = "test_main"]
+pub mod system;
 pub mod testing;
 
 #[cfg(feature = "threading")]
 mod threading;
+#[cfg(armv7m)]
+pub mod watchpoint;
 
 use riot_rs_debug::println;
 
@@ -35,6 +38,14 @@ cfg_if::cfg_if! {
         mod arch {
             #[cfg_attr(not(context = "riot-rs"), allow(dead_code))]
             pub fn init() {}
+
+            pub fn reset() -> ! {
+                unimplemented!();
+            }
+
+            pub fn halt() -> ! {
+                unimplemented!();
+            }
         }
     }
 }
@@ -58,14 +69,41 @@ fn panic(_info: &core::panic::PanicInfo) -> ! {
     loop {}
 }
 
+/// Returns the size of the ISR stack in bytes, see `CONFIG_ISR_STACKSIZE`.
+#[cfg(context = "cortex-m")]
+pub fn isr_stack_size() -> usize {
+    arch::isr_stack_size()
+}
+
+/// Returns the highest ISR stack usage since startup in bytes.
+///
+/// The ISR stack is painted at startup, this checks how much of it has been
+/// overwritten since.
+#[cfg(context = "cortex-m")]
+pub fn isr_stack_usage() -> usize {
+    arch::isr_stack_usage()
+}
+
 use linkme::distributed_slice;
 
+/// Functions run first thing in [`startup()`], e.g., by boards that need to set up pins or power
+/// rails before anything else touches the hardware.
+///
+/// They run before the architecture is initialized and before the debug console is available, so
+/// they must not print, use interrupts, or rely on any other subsystem.
+#[distributed_slice]
+pub static EARLY_INIT_FUNCS: [fn()] = [..];
+
 #[distributed_slice]
 pub static INIT_FUNCS: [fn()] = [..];
 
 #[inline]
 #[cfg_attr(not(context = "riot-rs"), allow(dead_code))]
 fn startup() -> ! {
+    for f in EARLY_INIT_FUNCS {
+        f();
+    }
+
     arch::init();
 
     #[cfg(feature = "debug-console")]
@@ -0,0 +1,64 @@
+//! System-wide power state control: reboot and shutdown.
+//!
+//! Subsystems that need to bring their peripherals or state into a defined
+//! condition before the system goes down register a hook in
+//! [`SHUTDOWN_FUNCS`].
+
+use linkme::distributed_slice;
+use riot_rs_debug::println;
+
+use crate::arch;
+
+/// Hooks run by [`reboot()`] and [`shutdown()`] before the system goes down.
+///
+/// The hooks are run in the reverse order of the slice, so that subsystems
+/// registering both an init and a shutdown function from the same crate are
+/// torn down in reverse init order.
+///
+/// Hooks are called with interrupts enabled, from the context that requested
+/// the reboot or shutdown. There is no timeout enforcement: hooks must not
+/// block and should return as soon as possible.
+#[distributed_slice]
+pub static SHUTDOWN_FUNCS: [fn()] = [..];
+
+/// Runs the shutdown hooks, then resets the system.
+pub fn reboot() -> ! {
+    println!("riot_rs_rt::system::reboot()");
+
+    run_shutdown_funcs();
+
+    arch::reset()
+}
+
+/// Runs the shutdown hooks, then halts the system.
+///
+/// Interrupts stay disabled, so the system only comes back up through an
+/// external reset.
+pub fn shutdown() -> ! {
+    println!("riot_rs_rt::system::shutdown()");
+
+    run_shutdown_funcs();
+
+    arch::halt()
+}
+
+/// Runs the shutdown hooks, then resets into the chip's USB bootloader.
+///
+/// This allows reflashing a device over USB without physical access to its
+/// buttons.
+///
+/// Supported on nRF52 (boards with a UF2 bootloader) and RP2040.
+#[cfg(any(context = "nrf52", context = "rp2040"))]
+pub fn reboot_to_bootloader() -> ! {
+    println!("riot_rs_rt::system::reboot_to_bootloader()");
+
+    run_shutdown_funcs();
+
+    arch::reset_to_bootloader()
+}
+
+fn run_shutdown_funcs() {
+    for f in SHUTDOWN_FUNCS.iter().rev() {
+        f();
+    }
+}
@@ -0,0 +1,145 @@
+//! Provides data watchpoints, using the comparators of the Cortex-M Data Watchpoint and Trace
+//! (DWT) unit.
+//!
+//! A watchpoint triggers a panic as soon as the watched memory is accessed, naming the watched
+//! address, which helps tracking down memory corruption without an attached debugger.
+//!
+//! This relies on the DebugMonitor exception and is thus only available on ARMv7-M.
+//! While a debugger has halting debug enabled, it takes precedence and halts the core instead.
+
+use core::ptr::{read_volatile, write_volatile};
+
+/// Debug Exception and Monitor Control Register.
+const DEMCR: *mut u32 = 0xE000_EDFC as *mut u32;
+const DEMCR_TRCENA: u32 = 1 << 24;
+const DEMCR_MON_EN: u32 = 1 << 16;
+
+/// DWT Control Register.
+const DWT_CTRL: *const u32 = 0xE000_1000 as *const u32;
+/// Address of the first comparator's `DWT_COMP`, followed by `DWT_MASK` and `DWT_FUNCTION`.
+const DWT_COMP0: usize = 0xE000_1020;
+const DWT_COMP_STRIDE: usize = 0x10;
+
+const DWT_FUNCTION_MATCHED: u32 = 1 << 24;
+const DWT_FUNCTION_MASK: u32 = 0xF;
+
+/// The kind of memory accesses a [`Watchpoint`] triggers on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl Access {
+    /// Returns the value of `DWT_FUNCTION.FUNCTION` for data address comparison.
+    fn function(self) -> u32 {
+        match self {
+            Access::Read => 0b0101,
+            Access::Write => 0b0110,
+            Access::ReadWrite => 0b0111,
+        }
+    }
+}
+
+/// Possible errors when setting a watchpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// All DWT comparators are in use.
+    NoFreeComparator,
+    /// The watched range is not naturally aligned.
+    ///
+    /// Comparators can only watch power-of-two sized ranges aligned to their size.
+    Unaligned,
+}
+
+/// An active watchpoint.
+///
+/// The watchpoint stays active until it is [cleared](Watchpoint::clear).
+#[derive(Debug)]
+pub struct Watchpoint {
+    comparator: usize,
+}
+
+impl Watchpoint {
+    /// Disables the watchpoint, freeing its comparator.
+    pub fn clear(self) {
+        cortex_m::interrupt::free(|_| {
+            // SAFETY: `self.comparator` is a comparator that exists and belongs to this
+            // watchpoint.
+            unsafe { write_volatile(function_reg(self.comparator), 0) };
+        });
+    }
+}
+
+/// Watches `value` for `access`.
+///
+/// # Errors
+///
+/// Returns [`Error::Unaligned`] if `value` is not aligned to its size rounded up to the next power
+/// of two, [`Error::NoFreeComparator`] if no comparator is available.
+pub fn watch<T>(value: &'static T, access: Access) -> Result<Watchpoint, Error> {
+    let size = core::mem::size_of::<T>().max(1).next_power_of_two();
+    watch_raw(value as *const T as usize, size, access)
+}
+
+/// Watches the `size` bytes at `addr` for `access`.
+///
+/// # Errors
+///
+/// Returns [`Error::Unaligned`] if `size` is not a power of two or `addr` is not aligned to `size`,
+/// [`Error::NoFreeComparator`] if no comparator is available.
+pub fn watch_raw(addr: usize, size: usize, access: Access) -> Result<Watchpoint, Error> {
+    if !size.is_power_of_two() || addr % size != 0 {
+        return Err(Error::Unaligned);
+    }
+
+    cortex_m::interrupt::free(|_| {
+        // SAFETY: reading and writing the DEMCR and DWT registers has no side effects besides
+        // enabling the DWT and the DebugMonitor exception, and configuring the comparator found
+        // to be unused.
+        unsafe {
+            write_volatile(DEMCR, read_volatile(DEMCR) | DEMCR_TRCENA | DEMCR_MON_EN);
+
+            let numcomp = (read_volatile(DWT_CTRL) >> 28) as usize;
+            let comparator = (0..numcomp)
+                .find(|&i| read_volatile(function_reg(i)) & DWT_FUNCTION_MASK == 0)
+                .ok_or(Error::NoFreeComparator)?;
+
+            write_volatile(comp_reg(comparator), addr as u32);
+            write_volatile(mask_reg(comparator), size.trailing_zeros());
+            write_volatile(function_reg(comparator), access.function());
+
+            Ok(Watchpoint { comparator })
+        }
+    })
+}
+
+fn comp_reg(comparator: usize) -> *mut u32 {
+    (DWT_COMP0 + comparator * DWT_COMP_STRIDE) as *mut u32
+}
+
+fn mask_reg(comparator: usize) -> *mut u32 {
+    (DWT_COMP0 + comparator * DWT_COMP_STRIDE + 0x4) as *mut u32
+}
+
+fn function_reg(comparator: usize) -> *mut u32 {
+    (DWT_COMP0 + comparator * DWT_COMP_STRIDE + 0x8) as *mut u32
+}
+
+#[allow(non_snake_case)]
+#[cortex_m_rt::exception]
+fn DebugMonitor() {
+    // SAFETY: reading the DWT registers has no side effects besides clearing the `MATCHED` bits.
+    let hit = unsafe {
+        let numcomp = (read_volatile(DWT_CTRL) >> 28) as usize;
+        (0..numcomp)
+            .find(|&i| read_volatile(function_reg(i)) & DWT_FUNCTION_MATCHED != 0)
+            .map(|i| read_volatile(comp_reg(i)))
+    };
+
+    match hit {
+        Some(addr) => panic!("watchpoint hit: access to {:#010x}", addr),
+        None => panic!("unexpected debug monitor event"),
+    }
+}
@@ -96,3 +96,135 @@ mod tests {
         assert_eq!(runqueue.get_next(), Some(ThreadId::new(0)));
     }
 }
+
+#[cfg(test)]
+mod model_tests {
+    //! Model-based tests: random operation sequences are applied both to a
+    //! [`RunQueue`] and to a trivially correct reference model, comparing the
+    //! scheduling decision after every step.
+
+    use std::collections::VecDeque;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const N_QUEUES: usize = 8;
+    const N_THREADS: usize = 32;
+
+    #[derive(Clone, Copy, Debug)]
+    enum Op {
+        Add(u8, u8),
+        AddHead(u8, u8),
+        DelHead(u8),
+        Remove(u8, u8),
+        Advance(u8),
+    }
+
+    fn op() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            (0..N_THREADS as u8, 0..N_QUEUES as u8).prop_map(|(n, rq)| Op::Add(n, rq)),
+            (0..N_THREADS as u8, 0..N_QUEUES as u8).prop_map(|(n, rq)| Op::AddHead(n, rq)),
+            (0..N_QUEUES as u8).prop_map(Op::DelHead),
+            (0..N_THREADS as u8, 0..N_QUEUES as u8).prop_map(|(n, rq)| Op::Remove(n, rq)),
+            (0..N_QUEUES as u8).prop_map(Op::Advance),
+        ]
+    }
+
+    /// Reference model: one FIFO per runqueue.
+    struct Model {
+        queues: Vec<VecDeque<u8>>,
+    }
+
+    impl Model {
+        fn new() -> Self {
+            Self {
+                queues: (0..N_QUEUES).map(|_| VecDeque::new()).collect(),
+            }
+        }
+
+        fn queue(&mut self, rq: u8) -> &mut VecDeque<u8> {
+            self.queues.get_mut(usize::from(rq)).unwrap()
+        }
+
+        fn is_queued(&self, n: u8) -> bool {
+            self.queues.iter().any(|q| q.contains(&n))
+        }
+
+        /// Head of the highest non-empty queue.
+        fn get_next(&self) -> Option<ThreadId> {
+            self.queues
+                .iter()
+                .rev()
+                .find_map(|q| q.front().copied())
+                .map(ThreadId::new)
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn runqueue_matches_model(ops in prop::collection::vec(op(), 0..512)) {
+            let mut runqueue: RunQueue<N_QUEUES, N_THREADS> = RunQueue::new();
+            let mut model = Model::new();
+
+            for op in ops {
+                match op {
+                    Op::Add(n, rq) => {
+                        // RIOT-rs only adds threads that are not queued yet,
+                        // i.e., a thread is never in two runqueues at once.
+                        if model.is_queued(n) {
+                            continue;
+                        }
+                        model.queue(rq).push_back(n);
+                        runqueue.add(ThreadId::new(n), RunqueueId::new(rq));
+                    }
+                    Op::AddHead(n, rq) => {
+                        if model.is_queued(n) {
+                            continue;
+                        }
+                        model.queue(rq).push_front(n);
+                        runqueue.add_head(ThreadId::new(n), RunqueueId::new(rq));
+                    }
+                    Op::DelHead(rq) => {
+                        // `del()` only supports removing a queue's head.
+                        if let Some(head) = model.queue(rq).pop_front() {
+                            runqueue.del(ThreadId::new(head), RunqueueId::new(rq));
+                        }
+                    }
+                    Op::Remove(n, rq) => {
+                        model.queue(rq).retain(|&queued| queued != n);
+                        runqueue.remove(ThreadId::new(n), RunqueueId::new(rq));
+                    }
+                    Op::Advance(rq) => {
+                        let queue = model.queue(rq);
+                        if !queue.is_empty() {
+                            queue.rotate_left(1);
+                        }
+                        runqueue.advance(RunqueueId::new(rq));
+                    }
+                }
+
+                prop_assert!(runqueue.is_consistent());
+                prop_assert_eq!(runqueue.get_next(), model.get_next());
+                for n in 0..N_THREADS as u8 {
+                    prop_assert_eq!(runqueue.is_queued(ThreadId::new(n)), model.is_queued(n));
+                }
+            }
+        }
+
+        #[test]
+        fn advance_round_robins_within_priority(n_threads in 1..N_THREADS as u8, rq in 0..N_QUEUES as u8) {
+            let mut runqueue: RunQueue<N_QUEUES, N_THREADS> = RunQueue::new();
+
+            for n in 0..n_threads {
+                runqueue.add(ThreadId::new(n), RunqueueId::new(rq));
+            }
+
+            // Two full rounds visit every thread in insertion order.
+            for n in (0..n_threads).chain(0..n_threads) {
+                prop_assert_eq!(runqueue.get_next(), Some(ThreadId::new(n)));
+                runqueue.advance(RunqueueId::new(rq));
+            }
+        }
+    }
+}
@@ -79,6 +79,17 @@ impl<const N_QUEUES: usize, const N_THREADS: usize> RunQueue<{ N_QUEUES }, { N_T
         self.queues.push(n.0, rq.0);
     }
 
+    /// Adds thread with pid `n` to the front of runqueue number `rq`, so that
+    /// it is the queue's head.
+    ///
+    /// Does nothing if `n` is already queued.
+    pub fn add_head(&mut self, n: ThreadId, rq: RunqueueId) {
+        debug_assert!(usize::from(n) < N_THREADS);
+        debug_assert!(usize::from(rq) < N_QUEUES);
+        self.bitcache |= 1 << rq.0;
+        self.queues.push_head(n.0, rq.0);
+    }
+
     /// Removes thread with pid `n` from runqueue number `rq`.
     ///
     /// # Panics
@@ -100,6 +111,20 @@ impl<const N_QUEUES: usize, const N_THREADS: usize> RunQueue<{ N_QUEUES }, { N_T
         (USIZE_BITS as u32 - val.leading_zeros()) as u32
     }
 
+    /// Removes thread with pid `n` from runqueue number `rq`, wherever it is
+    /// in the queue.
+    ///
+    /// Unlike [`RunQueue::del()`], `n` doesn't need to be the queue's head.
+    /// Does nothing if `n` is not in the queue.
+    pub fn remove(&mut self, n: ThreadId, rq: RunqueueId) {
+        debug_assert!(usize::from(n) < N_THREADS);
+        debug_assert!(usize::from(rq) < N_QUEUES);
+        self.queues.remove(n.0, rq.0);
+        if self.queues.is_empty(rq.0) {
+            self.bitcache &= !(1 << rq.0);
+        }
+    }
+
     /// Returns the pid that should run next.
     ///
     /// Returns the next runnable thread of
@@ -123,6 +148,33 @@ impl<const N_QUEUES: usize, const N_THREADS: usize> RunQueue<{ N_QUEUES }, { N_T
         debug_assert!((usize::from(rq)) < N_QUEUES);
         self.queues.advance(rq.0)
     }
+
+    /// Returns whether thread with pid `n` is in any of the queues.
+    pub fn is_queued(&self, n: ThreadId) -> bool {
+        debug_assert!(usize::from(n) < N_THREADS);
+        self.queues.is_queued(n.0)
+    }
+
+    /// Checks the runqueue invariants.
+    ///
+    /// Returns `true` if
+    /// - the bitcache has exactly the bits of the non-empty queues set,
+    /// - every queue is a well-formed circular list,
+    /// - no thread is part of more than one queue.
+    ///
+    /// This is written without early returns so that it stays within the subset
+    /// of Rust that hax can extract.
+    pub fn is_consistent(&self) -> bool {
+        let mut consistent = self.queues.is_consistent();
+        if N_QUEUES < USIZE_BITS {
+            consistent = consistent && self.bitcache >> N_QUEUES == 0;
+        }
+        for rq in 0..N_QUEUES {
+            let bit_set = self.bitcache & (1 << rq) != 0;
+            consistent = consistent && bit_set != self.queues.is_empty(rq as u8);
+        }
+        consistent
+    }
 }
 
 mod clist {
@@ -173,6 +225,21 @@ mod clist {
             }
         }
 
+        pub fn push_head(&mut self, n: u8, rq: u8) {
+            assert!(n < Self::sentinel());
+            if self.next_idxs[n as usize] == Self::sentinel() {
+                if self.tail[rq as usize] == Self::sentinel() {
+                    // rq is empty, link both tail and n.next to n
+                    self.tail[rq as usize] = n;
+                    self.next_idxs[n as usize] = n;
+                } else {
+                    // insert n between tail and the old head, keeping the tail
+                    self.next_idxs[n as usize] = self.next_idxs[self.tail[rq as usize] as usize];
+                    self.next_idxs[self.tail[rq as usize] as usize] = n;
+                }
+            }
+        }
+
         pub fn pop_head(&mut self, rq: u8) -> Option<u8> {
             if self.tail[rq as usize] == Self::sentinel() {
                 // rq is empty, do nothing
@@ -196,6 +263,32 @@ mod clist {
             }
         }
 
+        pub fn remove(&mut self, n: u8, rq: u8) {
+            let tail = self.tail[rq as usize];
+            if tail != Self::sentinel() && self.next_idxs[n as usize] != Self::sentinel() {
+                // Find `n`'s predecessor. If `n` is not in this list, this ends
+                // on an element that is not followed by `n`.
+                let mut prev = tail;
+                for _ in 0..N_THREADS {
+                    if self.next_idxs[prev as usize] != n {
+                        prev = self.next_idxs[prev as usize];
+                    }
+                }
+                if self.next_idxs[prev as usize] == n {
+                    if prev == n {
+                        // `n` is the only entry.
+                        self.tail[rq as usize] = Self::sentinel();
+                    } else {
+                        self.next_idxs[prev as usize] = self.next_idxs[n as usize];
+                        if tail == n {
+                            self.tail[rq as usize] = prev;
+                        }
+                    }
+                    self.next_idxs[n as usize] = Self::sentinel();
+                }
+            }
+        }
+
         pub fn peek_head(&self, rq: u8) -> Option<u8> {
             if self.tail[rq as usize] == Self::sentinel() {
                 None
@@ -209,6 +302,50 @@ mod clist {
                 self.tail[rq as usize] = self.next_idxs[self.tail[rq as usize] as usize];
             }
         }
+
+        pub fn is_queued(&self, n: u8) -> bool {
+            self.next_idxs[n as usize] != Self::sentinel()
+        }
+
+        /// Checks that every non-empty list is a cycle through its tail, that
+        /// no element is part of more than one list, and that elements not in
+        /// any list are unlinked.
+        // Range loops are kept as hax extracts them to folds.
+        #[allow(clippy::needless_range_loop)]
+        pub fn is_consistent(&self) -> bool {
+            let mut consistent = true;
+            let mut seen = [false; N_THREADS];
+            for rq in 0..N_QUEUES {
+                let tail = self.tail[rq];
+                if tail != Self::sentinel() {
+                    // Following the next pointers from the tail must lead back
+                    // to it within `N_THREADS` steps, visiting only elements
+                    // that are not part of any other list.
+                    let mut cur = tail;
+                    let mut closed = false;
+                    for _ in 0..N_THREADS {
+                        if consistent && !closed {
+                            if (cur as usize) < N_THREADS {
+                                cur = self.next_idxs[cur as usize];
+                            }
+                            if (cur as usize) < N_THREADS && !seen[cur as usize] {
+                                seen[cur as usize] = true;
+                                closed = cur == tail;
+                            } else {
+                                consistent = false;
+                            }
+                        }
+                    }
+                    consistent = consistent && closed;
+                }
+            }
+            for n in 0..N_THREADS {
+                if !seen[n] && self.next_idxs[n] != Self::sentinel() {
+                    consistent = false;
+                }
+            }
+            consistent
+        }
     }
 
     #[cfg(test)]
@@ -277,6 +414,58 @@ mod clist {
             assert!(clist.is_empty(0));
         }
 
+        #[test]
+        fn test_clist_is_consistent() {
+            let mut clist: CList<8, 32> = CList::new();
+            assert!(clist.is_consistent());
+            clist.push(0, 0);
+            clist.push(1, 0);
+            clist.push(2, 1);
+            assert!(clist.is_consistent());
+
+            // link element 2 into queue 0's cycle as well
+            clist.next_idxs[1] = 2;
+            clist.next_idxs[2] = 0;
+            assert!(!clist.is_consistent());
+        }
+
+        #[test]
+        fn test_clist_remove() {
+            let mut clist: CList<8, 32> = CList::new();
+            for i in 0..4 {
+                clist.push(i, 0);
+            }
+            clist.push(4, 1);
+
+            // Not in this list.
+            clist.remove(4, 0);
+            // Middle, tail, head.
+            clist.remove(2, 0);
+            clist.remove(3, 0);
+            assert!(clist.is_consistent());
+            clist.remove(0, 0);
+            assert!(clist.is_consistent());
+
+            assert_eq!(clist.pop_head(0), Some(1));
+            assert!(clist.is_empty(0));
+            assert_eq!(clist.pop_head(1), Some(4));
+            assert!(clist.is_consistent());
+        }
+
+        #[test]
+        fn test_clist_push_head() {
+            let mut clist: CList<8, 32> = CList::new();
+            clist.push_head(0, 0);
+            clist.push(1, 0);
+            clist.push_head(2, 0);
+            assert!(clist.is_consistent());
+
+            assert_eq!(clist.pop_head(0), Some(2));
+            assert_eq!(clist.pop_head(0), Some(0));
+            assert_eq!(clist.pop_head(0), Some(1));
+            assert!(clist.is_empty(0));
+        }
+
         #[test]
         fn test_clist_peek_head() {
             let mut clist: CList<8, 32> = CList::new();
@@ -9,6 +9,9 @@ use crate::{cleanup, THREADS};
 #[cfg(not(any(armv6m, armv7m, armv8m)))]
 compile_error!("no supported ARM variant selected");
 
+#[cfg(all(feature = "sched-latency", armv6m))]
+compile_error!("`sched-latency` needs a cycle counter, which Cortex-M0(+) lacks");
+
 pub struct Cpu;
 
 impl Arch for Cpu {
@@ -57,12 +60,22 @@ impl Arch for Cpu {
     /// Triggers a PendSV exception.
     #[inline(always)]
     fn schedule() {
+        #[cfg(feature = "sched-latency")]
+        crate::sched_latency::record_trigger(cortex_m::peripheral::DWT::cycle_count());
         SCB::set_pendsv();
         cortex_m::asm::isb();
     }
 
     #[inline(always)]
     fn start_threading() {
+        #[cfg(feature = "sched-latency")]
+        {
+            // SAFETY: only enables the cycle counter, which nothing else configures.
+            let mut p = unsafe { cortex_m::Peripherals::steal() };
+            p.DCB.enable_trace();
+            p.DWT.enable_cycle_counter();
+            crate::sched_latency::start();
+        }
         Self::schedule();
     }
 }
@@ -178,12 +191,24 @@ unsafe fn sched() -> u128 {
                 }
             };
 
+            #[cfg(feature = "sched-latency")]
+            crate::sched_latency::record_sched(
+                cortex_m::peripheral::DWT::cycle_count(),
+                threads.current_pid() != Some(next_pid),
+            );
+
+            #[cfg(feature = "sched-debug")]
+            threads.check_invariants(next_pid);
+
             let current_high_regs;
             if let Some(current_pid) = threads.current_pid() {
                 if next_pid == current_pid {
                     return Some(0);
                 }
 
+                #[cfg(feature = "stack-canary")]
+                threads.check_stack_canary(current_pid);
+
                 threads.threads[usize::from(current_pid)].sp =
                     cortex_m::register::psp::read() as usize;
                 threads.current_thread = Some(next_pid);
@@ -9,6 +9,9 @@ use esp_hal::{
     riscv, Cpu as EspHalCpu,
 };
 
+#[cfg(feature = "sched-latency")]
+compile_error!("`sched-latency` is only supported on Cortex-M3 and higher");
+
 pub struct Cpu;
 
 impl Arch for Cpu {
@@ -132,10 +135,16 @@ unsafe fn sched(trap_frame: &mut TrapFrame) {
                 }
             };
 
+            #[cfg(feature = "sched-debug")]
+            threads.check_invariants(next_pid);
+
             if let Some(current_pid) = threads.current_pid() {
                 if next_pid == current_pid {
                     return true;
                 }
+
+                #[cfg(feature = "stack-canary")]
+                threads.check_stack_canary(current_pid);
                 copy_registers(
                     trap_frame,
                     &mut threads.threads[usize::from(current_pid)].data,
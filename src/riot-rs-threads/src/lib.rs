@@ -13,7 +13,11 @@ mod threadlist;
 
 pub mod channel;
 pub mod lock;
+pub mod priority;
+#[cfg(feature = "sched-latency")]
+pub mod sched_latency;
 pub mod thread_flags;
+pub mod watch;
 
 #[doc(hidden)]
 pub mod macro_reexports {
@@ -37,6 +41,33 @@ pub const SCHED_PRIO_LEVELS: usize = 12;
 /// a global defining the number of threads that can be created
 pub const THREADS_NUMOF: usize = 16;
 
+/// The number of cores threads are scheduled on.
+///
+/// The scheduler is single-core for now.
+pub const CORES_NUMOF: usize = 1;
+
+/// Identifies a CPU core, in `0..`[`CORES_NUMOF`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CoreId(u8);
+
+impl CoreId {
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+}
+
+impl From<CoreId> for usize {
+    fn from(value: CoreId) -> Self {
+        value.0 as usize
+    }
+}
+
+/// The scheduler state.
+///
+/// Every access happens inside a critical section, i.e., with interrupts
+/// disabled on single-core systems. Work done while holding it must stay
+/// bounded; see the "Critical sections" section of the README for the current
+/// worst cases.
 static THREADS: EnsureOnce<Threads> = EnsureOnce::new(Threads::new());
 
 pub type ThreadFn = fn();
@@ -52,7 +83,13 @@ struct Threads {
     threads: [Thread; THREADS_NUMOF],
     /// `Some` when a thread is blocking another thread due to conflicting
     /// resource access.
+    ///
+    /// Links each thread in a [`ThreadList`](threadlist::ThreadList) to the
+    /// next one.
     thread_blocklist: [Option<ThreadId>; THREADS_NUMOF],
+    /// For each thread waiting in [`thread_flags::wait_any_from()`], the
+    /// thread it boosts.
+    flag_boosts: [Option<ThreadId>; THREADS_NUMOF],
     /// The currently running thread.
     current_thread: Option<ThreadId>,
 }
@@ -63,6 +100,7 @@ impl Threads {
             runqueue: RunQueue::new(),
             threads: [const { Thread::default() }; THREADS_NUMOF],
             thread_blocklist: [const { None }; THREADS_NUMOF],
+            flag_boosts: [const { None }; THREADS_NUMOF],
             current_thread: None,
         }
     }
@@ -84,28 +122,21 @@ impl Threads {
         self.current_thread
     }
 
-    /// Creates a new thread.
+    /// Reserves a thread slot for a new thread.
     ///
-    /// This sets up the stack and TCB for this thread.
+    /// The slot is marked as [`ThreadState::Creating`], so it is neither handed
+    /// out again nor considered by the scheduler, and can't be woken up, until
+    /// its stack has been set up and the thread is made runnable.
     ///
     /// Returns `None` if there is no free thread slot.
-    fn create(
-        &mut self,
-        func: usize,
-        arg: usize,
-        stack: &'static mut [u8],
-        prio: RunqueueId,
-    ) -> Option<&mut Thread> {
-        if let Some((thread, pid)) = self.get_unused() {
-            Cpu::setup_stack(thread, stack, func, arg);
-            thread.prio = prio;
-            thread.pid = pid;
-            thread.state = ThreadState::Paused;
-
-            Some(thread)
-        } else {
-            None
-        }
+    fn reserve(&mut self, prio: RunqueueId) -> Option<ThreadId> {
+        let (thread, pid) = self.get_unused()?;
+        thread.prio = prio;
+        thread.base_prio = prio;
+        thread.pid = pid;
+        thread.state = ThreadState::Creating;
+        thread.suspended = false;
+        Some(pid)
     }
 
     // fn get_unchecked(&self, thread_id: ThreadId) -> &Thread {
@@ -145,7 +176,7 @@ impl Threads {
     /// Sets the state of a thread.
     ///
     /// This function handles adding/ removing the thread to the Runqueue depending
-    /// on its previous or new state.
+    /// on its previous or new state, unless the thread is suspended.
     ///
     /// # Panics
     ///
@@ -154,7 +185,9 @@ impl Threads {
         let thread = &mut self.threads[usize::from(pid)];
         let old_state = thread.state;
         thread.state = state;
-        if old_state != ThreadState::Running && state == ThreadState::Running {
+        if thread.suspended {
+            // Suspended threads are never in the runqueue.
+        } else if old_state != ThreadState::Running && state == ThreadState::Running {
             self.runqueue.add(thread.pid, thread.prio);
         } else if old_state == ThreadState::Running && state != ThreadState::Running {
             self.runqueue.del(thread.pid, thread.prio);
@@ -163,6 +196,165 @@ impl Threads {
         old_state
     }
 
+    /// Changes the priority of a thread.
+    ///
+    /// If the thread is in the runqueue, it is moved to the runqueue of its new
+    /// priority: the current thread at the head, so that it keeps running
+    /// instead of yielding to threads of the same priority, any other thread at
+    /// the tail.
+    ///
+    /// Returns the thread's previous priority.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `thread_id` is >= [`THREADS_NUMOF`].
+    ///
+    /// A thread waiting in a [`ThreadList`](threadlist::ThreadList) keeps its
+    /// position there, so the caller must keep the list in priority order.
+    fn set_priority(&mut self, thread_id: ThreadId, prio: RunqueueId) -> RunqueueId {
+        let thread = &mut self.threads[usize::from(thread_id)];
+        let old_prio = thread.prio;
+        if old_prio == prio {
+            return old_prio;
+        }
+        thread.prio = prio;
+        if thread.state == ThreadState::Running && !thread.suspended {
+            // The thread isn't necessarily the head of its runqueue.
+            self.runqueue.remove(thread_id, old_prio);
+            if self.current_thread == Some(thread_id) {
+                self.runqueue.add_head(thread_id, prio);
+            } else {
+                self.runqueue.add(thread_id, prio);
+            }
+        }
+        old_prio
+    }
+
+    /// Raises the base priority of the current thread to at least `prio`, see
+    /// [`with_boosted_priority()`].
+    ///
+    /// Returns the previous base priority, to be passed to
+    /// [`Threads::restore_current_priority()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no current thread.
+    fn boost_current(&mut self, prio: RunqueueId) -> RunqueueId {
+        let pid = self.current_pid().unwrap();
+        let thread = self.current().unwrap();
+        let old_base_prio = thread.base_prio;
+        // The boost applies to the base priority, so that it composes with
+        // boosts by threads waiting in `thread_flags::wait_any_from()`.
+        thread.base_prio = old_base_prio.max(prio);
+        self.update_priority(pid);
+        old_base_prio
+    }
+
+    /// Restores the base priority of the current thread after
+    /// [`Threads::boost_current()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no current thread.
+    fn restore_current_priority(&mut self, base_prio: RunqueueId) {
+        let pid = self.current_pid().unwrap();
+        self.current().unwrap().base_prio = base_prio;
+        self.update_priority(pid);
+    }
+
+    /// Updates the priority of a thread to the highest of its base priority and
+    /// the priorities of the threads boosting it, see
+    /// [`thread_flags::wait_any_from()`].
+    ///
+    /// The priority of a thread waiting in a [`ThreadList`](threadlist::ThreadList)
+    /// is only changed as far as the list's order allows: it is not raised, and
+    /// not lowered below the priority of the thread queued behind it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `thread_id` is >= [`THREADS_NUMOF`].
+    fn update_priority(&mut self, thread_id: ThreadId) {
+        let mut prio = self.threads[usize::from(thread_id)].base_prio;
+        for (waiter, setter) in self.flag_boosts.iter().enumerate() {
+            if *setter == Some(thread_id) {
+                prio = prio.max(self.threads[waiter].prio);
+            }
+        }
+        let thread = &self.threads[usize::from(thread_id)];
+        if thread.state.is_list_blocked() {
+            prio = prio.min(thread.prio);
+            if let Some(next) = self.thread_blocklist[usize::from(thread_id)] {
+                prio = prio.max(self.threads[usize::from(next)].prio);
+            }
+        }
+        self.set_priority(thread_id, prio);
+    }
+
+    /// Marks a thread as suspended and takes it out of the runqueue, see
+    /// [`suspend()`].
+    ///
+    /// Returns `false` if no thread exists for `thread_id` or if it is already
+    /// suspended.
+    fn suspend(&mut self, thread_id: ThreadId) -> bool {
+        if !self.is_valid_pid(thread_id) {
+            return false;
+        }
+        let thread = self.get_unchecked_mut(thread_id);
+        if thread.suspended {
+            return false;
+        }
+        thread.suspended = true;
+        if thread.state == ThreadState::Running {
+            let prio = thread.prio;
+            self.runqueue.remove(thread_id, prio);
+        }
+        true
+    }
+
+    /// Clears the suspended mark of a thread and puts it back into the runqueue
+    /// if it is ready to run, see [`resume()`].
+    ///
+    /// Returns `false` if no thread exists for `thread_id` or if it is not
+    /// suspended.
+    fn resume(&mut self, thread_id: ThreadId) -> bool {
+        if !self.is_valid_pid(thread_id) {
+            return false;
+        }
+        let thread = self.get_unchecked_mut(thread_id);
+        if !thread.suspended {
+            return false;
+        }
+        thread.suspended = false;
+        if thread.state == ThreadState::Running {
+            let prio = thread.prio;
+            self.runqueue.add(thread_id, prio);
+        }
+        true
+    }
+
+    /// Moves `target` to the head of the current thread's runqueue, see
+    /// [`yield_to()`].
+    ///
+    /// Returns `false` if `target` is the current thread, not ready, suspended,
+    /// or of a different priority.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no current thread.
+    fn yield_to(&mut self, target: ThreadId) -> bool {
+        let prio = self.current().unwrap().prio;
+        if !self.is_valid_pid(target) || self.current_pid() == Some(target) {
+            return false;
+        }
+        let thread = self.get_unchecked_mut(target);
+        if thread.state != ThreadState::Running || thread.suspended || thread.prio != prio {
+            return false;
+        }
+        self.runqueue.remove(target, prio);
+        self.runqueue.add_head(target, prio);
+        true
+    }
+
     /// Returns the state of a thread.
     fn get_state(&self, thread_id: ThreadId) -> Option<ThreadState> {
         if self.is_valid_pid(thread_id) {
@@ -171,6 +363,93 @@ impl Threads {
             None
         }
     }
+
+    /// Checks that the stack canary of a thread is intact.
+    ///
+    /// Called on every context switch for the thread being switched away from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the canary was overwritten, i.e., if the thread has overflown
+    /// its stack.
+    #[cfg(feature = "stack-canary")]
+    #[cfg_attr(not(context = "riot-rs"), allow(dead_code))]
+    fn check_stack_canary(&self, thread_id: ThreadId) {
+        let thread = &self.threads[usize::from(thread_id)];
+        if thread.state == ThreadState::Invalid {
+            // The thread has ended, its stack is not in use anymore.
+            return;
+        }
+        // SAFETY: `canary` was set up by `write_stack_canary()` to point into the
+        // thread's (static) stack.
+        let canary = unsafe { (thread.canary as *const usize).read_volatile() };
+        assert!(
+            canary == STACK_CANARY,
+            "stack overflow: thread {thread_id:?} overwrote its stack canary"
+        );
+    }
+
+    /// Checks the scheduler invariants, given the thread that is about to be
+    /// switched to.
+    ///
+    /// Called on every scheduler invocation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the following does not hold:
+    /// - the runqueue is internally consistent,
+    /// - `next_pid` is in [`ThreadState::Running`],
+    /// - exactly the threads in [`ThreadState::Running`] that are not suspended
+    ///   are in the runqueue,
+    /// - only threads blocked on a [`ThreadList`](threadlist::ThreadList) are
+    ///   linked in the blocklist, each to a thread blocked in the same way and
+    ///   of the same or lower priority, with no thread being linked to twice.
+    #[cfg(feature = "sched-debug")]
+    #[cfg_attr(not(context = "riot-rs"), allow(dead_code))]
+    fn check_invariants(&self, next_pid: ThreadId) {
+        assert!(
+            self.runqueue.is_consistent(),
+            "sched-debug: inconsistent runqueue"
+        );
+
+        let next_state = self.threads[usize::from(next_pid)].state;
+        assert!(
+            next_state == ThreadState::Running,
+            "sched-debug: next thread {next_pid:?} is in state {next_state:?}"
+        );
+
+        let mut linked = [false; THREADS_NUMOF];
+        for (i, thread) in self.threads.iter().enumerate() {
+            let pid = ThreadId::new(i as u8);
+            let is_runnable = thread.state == ThreadState::Running && !thread.suspended;
+            let is_queued = self.runqueue.is_queued(pid);
+            assert!(
+                is_runnable == is_queued,
+                "sched-debug: thread {pid:?} in state {:?} (suspended: {}) is queued: {is_queued}",
+                thread.state,
+                thread.suspended
+            );
+
+            if let Some(next) = self.thread_blocklist[i] {
+                let next_state = self.threads[usize::from(next)].state;
+                assert!(
+                    thread.state.is_list_blocked()
+                        && core::mem::discriminant(&thread.state)
+                            == core::mem::discriminant(&next_state),
+                    "sched-debug: thread {pid:?} in state {:?} is linked to thread {next:?} in state {next_state:?}",
+                    thread.state
+                );
+                assert!(
+                    !core::mem::replace(&mut linked[usize::from(next)], true),
+                    "sched-debug: thread {next:?} is linked to twice in the blocklist"
+                );
+                assert!(
+                    self.threads[usize::from(next)].prio <= thread.prio,
+                    "sched-debug: thread {pid:?} is linked to thread {next:?} of higher priority"
+                );
+            }
+        }
+    }
 }
 
 /// Starts threading.
@@ -220,6 +499,14 @@ impl<T> Arguable for &'static T {
 ///
 /// This sets up the stack for the thread and adds it to
 /// the runqueue.
+///
+/// This may be called from thread mode, ISRs and embassy tasks. Once threading
+/// has started, the scheduler is triggered so that a new thread with a higher
+/// priority than the current one runs right away, or as soon as the ISR returns.
+///
+/// # Panics
+///
+/// Panics if all [`THREADS_NUMOF`] thread slots are in use.
 pub fn thread_create<T: Arguable + Send>(
     func: fn(arg: T),
     arg: T,
@@ -230,7 +517,9 @@ pub fn thread_create<T: Arguable + Send>(
     unsafe { thread_create_raw(func as usize, arg, stack, prio) }
 }
 
-/// Low-level function to create a thread without argument
+/// Low-level function to create a thread without argument.
+///
+/// See [`thread_create()`] for the contexts this may be called from.
 pub fn thread_create_noarg(func: fn(), stack: &'static mut [u8], prio: u8) -> ThreadId {
     unsafe { thread_create_raw(func as usize, 0, stack, prio) }
 }
@@ -245,14 +534,57 @@ pub unsafe fn thread_create_raw(
     stack: &'static mut [u8],
     prio: u8,
 ) -> ThreadId {
+    let thread_id = THREADS
+        .with_mut(|mut threads| threads.reserve(RunqueueId::new(prio)))
+        .unwrap();
+
+    // Setting up the stack doesn't touch any scheduler state, so do it outside of
+    // the critical section. Only the resulting context is copied into the
+    // reserved slot.
+    let mut thread = Thread::default();
+    #[cfg(feature = "stack-canary")]
+    {
+        thread.canary = write_stack_canary(stack);
+    }
+    Cpu::setup_stack(&mut thread, stack, func, arg);
+
     THREADS.with_mut(|mut threads| {
-        let thread_id = threads
-            .create(func, arg, stack, RunqueueId::new(prio))
-            .unwrap()
-            .pid;
+        let reserved = threads.get_unchecked_mut(thread_id);
+        reserved.sp = thread.sp;
+        reserved.data = thread.data;
+        #[cfg(feature = "stack-canary")]
+        {
+            reserved.canary = thread.canary;
+        }
         threads.set_state(thread_id, ThreadState::Running);
-        thread_id
-    })
+        // Before threading has started, the scheduler will be invoked by
+        // `start_threading()` anyway.
+        if threads.current_pid().is_some() {
+            schedule();
+        }
+    });
+
+    thread_id
+}
+
+/// Value written to the bottom of each thread's stack, see [`Threads::check_stack_canary()`].
+#[cfg(feature = "stack-canary")]
+const STACK_CANARY: usize = 0xDEADBEEF;
+
+/// Writes [`STACK_CANARY`] to the lowest aligned word of `stack`.
+///
+/// Returns the canary's address.
+///
+/// # Panics
+///
+/// Panics if `stack` is too small to hold the canary.
+#[cfg(feature = "stack-canary")]
+fn write_stack_canary(stack: &mut [u8]) -> usize {
+    let offset = stack.as_ptr().align_offset(core::mem::align_of::<usize>());
+    let canary = stack[offset..][..core::mem::size_of::<usize>()].as_mut_ptr() as *mut usize;
+    // SAFETY: the pointer is aligned and points to `size_of::<usize>()` bytes within `stack`.
+    unsafe { canary.write_volatile(STACK_CANARY) };
+    canary as usize
 }
 
 /// Returns the [`ThreadId`] of the currently active thread.
@@ -263,6 +595,34 @@ pub fn current_pid() -> Option<ThreadId> {
     THREADS.with(|threads| threads.current_pid())
 }
 
+/// Returns the [`ThreadId`] of the thread currently active on `core`.
+///
+/// Returns `None` if no thread runs on that core (yet), or if `core` does not
+/// exist.
+///
+/// Note: when called from ISRs, this will return the thread id of the thread
+/// that was interrupted.
+pub fn current_tid_on(core: CoreId) -> Option<ThreadId> {
+    if usize::from(core) >= CORES_NUMOF {
+        return None;
+    }
+    // Single-core: the only core is the one running the current thread.
+    current_pid()
+}
+
+/// Returns the core `thread_id` is currently running on.
+///
+/// Returns `None` if the thread isn't currently active on any core, even if it
+/// is ready to run.
+pub fn is_running(thread_id: ThreadId) -> Option<CoreId> {
+    THREADS.with(|threads| (threads.current_pid() == Some(thread_id)).then_some(CoreId::new(0)))
+}
+
+/// Returns the id of the core this is called on.
+pub fn core_id() -> CoreId {
+    CoreId::new(0)
+}
+
 /// Checks if a given [`ThreadId`] is valid
 pub fn is_valid_pid(thread_id: ThreadId) -> bool {
     THREADS.with(|threads| threads.is_valid_pid(thread_id))
@@ -277,6 +637,12 @@ fn cleanup() -> ! {
     THREADS.with_mut(|mut threads| {
         let thread_id = threads.current_pid().unwrap();
         threads.set_state(thread_id, ThreadState::Invalid);
+        // The id may be reused, so don't keep boosting it.
+        for setter in &mut threads.flag_boosts {
+            if *setter == Some(thread_id) {
+                *setter = None;
+            }
+        }
     });
 
     schedule();
@@ -293,6 +659,67 @@ pub fn yield_same() {
     })
 }
 
+/// Yields to `target` if it is ready to run and has the same priority as the
+/// current thread.
+///
+/// Unlike [`yield_same()`], which lets the next thread in round-robin order
+/// run, `target` is moved to the front of the runqueue and runs right away.
+/// The current thread runs again after it, e.g., once `target` blocks or yields.
+///
+/// Returns `false`, without yielding, if `target` is not ready, suspended, or
+/// of a different priority.
+///
+/// # Panics
+///
+/// Panics if called outside of a thread.
+pub fn yield_to(target: ThreadId) -> bool {
+    THREADS.with_mut(|mut threads| {
+        if !threads.yield_to(target) {
+            return false;
+        }
+        schedule();
+        true
+    })
+}
+
+/// Runs `f` with the current thread's priority raised to at least `prio`.
+///
+/// The previous priority is restored when `f` returns or unwinds. As a boost
+/// never lowers the priority, calls can be nested: inner calls with a lower
+/// `prio` than the surrounding one have no effect.
+///
+/// Neither raising nor restoring the priority makes the thread yield to threads
+/// of its (new) priority: it stays at the head of its runqueue.
+///
+/// This is meant for short, latency-critical code paths; permanently running
+/// at a high priority starves lower priority threads.
+///
+/// # Panics
+///
+/// Panics if called outside of a thread or if `prio` is >= [`SCHED_PRIO_LEVELS`].
+pub fn with_boosted_priority<R>(prio: u8, f: impl FnOnce() -> R) -> R {
+    assert!(usize::from(prio) < SCHED_PRIO_LEVELS);
+
+    /// Restores the priority on drop, also when unwinding.
+    struct Restore(RunqueueId);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            THREADS.with_mut(|mut threads| {
+                threads.restore_current_priority(self.0);
+                // A thread with a priority between the boosted and the
+                // restored one may have become runnable in the meantime.
+                schedule();
+            });
+        }
+    }
+
+    let _restore =
+        THREADS.with_mut(|mut threads| Restore(threads.boost_current(RunqueueId::new(prio))));
+
+    f()
+}
+
 /// Suspends/ pauses the current thread's execution.
 pub fn sleep() {
     THREADS.with_mut(|mut threads| {
@@ -321,6 +748,44 @@ pub fn wakeup(thread_id: ThreadId) -> bool {
     })
 }
 
+/// Suspends a thread, which may be another thread than the current one.
+///
+/// The thread keeps its state, e.g., stays blocked on a lock or channel, but it
+/// won't be scheduled until it is [resumed](resume), even if it becomes ready to
+/// run in the meantime. This includes a thread that is still being created: it
+/// stays suspended once [`thread_create()`] made it ready to run.
+///
+/// Returns `false` if no thread exists for `thread_id` or if it is already
+/// suspended.
+pub fn suspend(thread_id: ThreadId) -> bool {
+    THREADS.with_mut(|mut threads| {
+        let is_current = threads.current_pid() == Some(thread_id);
+        if !threads.suspend(thread_id) {
+            return false;
+        }
+        if is_current {
+            schedule();
+        }
+        true
+    })
+}
+
+/// Resumes a thread suspended by [`suspend()`].
+///
+/// Returns `false` if no thread exists for `thread_id` or if it is not
+/// suspended.
+pub fn resume(thread_id: ThreadId) -> bool {
+    THREADS.with_mut(|mut threads| {
+        if !threads.resume(thread_id) {
+            return false;
+        }
+        if threads.get_unchecked_mut(thread_id).state == ThreadState::Running {
+            schedule();
+        }
+        true
+    })
+}
+
 /// Returns the size of the internal structure that holds the
 /// a thread's data.
 pub fn thread_struct_size() -> usize {
@@ -329,8 +794,157 @@ pub fn thread_struct_size() -> usize {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    /// Creates a runnable thread of priority `prio` in `threads`, without a stack.
+    pub(crate) fn spawn(threads: &mut Threads, prio: u8) -> ThreadId {
+        let pid = threads.reserve(RunqueueId::new(prio)).unwrap();
+        threads.set_state(pid, ThreadState::Running);
+        pid
+    }
+
     #[test]
     fn test_basic() {
         assert_eq!(1, 1);
     }
+
+    #[test]
+    fn test_boosted_priority_keeps_position() {
+        let mut threads = Threads::new();
+        let current = spawn(&mut threads, 2);
+        let other = spawn(&mut threads, 2);
+        threads.current_thread = Some(current);
+
+        let base_prio = threads.boost_current(RunqueueId::new(5));
+        assert_eq!(threads.current().unwrap().prio, RunqueueId::new(5));
+        assert_eq!(threads.runqueue.get_next(), Some(current));
+
+        threads.restore_current_priority(base_prio);
+        assert_eq!(threads.current().unwrap().prio, RunqueueId::new(2));
+        // Restoring doesn't yield to `other`.
+        assert_eq!(threads.runqueue.get_next(), Some(current));
+        threads.runqueue.advance(RunqueueId::new(2));
+        assert_eq!(threads.runqueue.get_next(), Some(other));
+        assert!(threads.runqueue.is_consistent());
+    }
+
+    /// Returns the threads in runqueue `prio`, in order, emptying it.
+    fn drain_runqueue(threads: &mut Threads, prio: RunqueueId) -> Vec<ThreadId> {
+        let mut order = Vec::new();
+        while let Some(pid) = threads.runqueue.get_next() {
+            assert_eq!(threads.get_unchecked_mut(pid).prio, prio);
+            threads.runqueue.del(pid, prio);
+            order.push(pid);
+        }
+        order
+    }
+
+    #[test]
+    fn test_yield_to() {
+        let mut threads = Threads::new();
+        let current = spawn(&mut threads, 2);
+        let second = spawn(&mut threads, 2);
+        let target = spawn(&mut threads, 2);
+        let last = spawn(&mut threads, 2);
+        threads.current_thread = Some(current);
+
+        assert!(threads.yield_to(target));
+        // `target` runs next, then the current thread, then the others in their
+        // previous order.
+        assert_eq!(
+            drain_runqueue(&mut threads, RunqueueId::new(2)),
+            [target, current, second, last]
+        );
+    }
+
+    #[test]
+    fn test_yield_to_not_ready() {
+        let mut threads = Threads::new();
+        let current = spawn(&mut threads, 2);
+        let other_prio = spawn(&mut threads, 3);
+        let blocked = threads.reserve(RunqueueId::new(2)).unwrap();
+        threads.set_state(blocked, ThreadState::Paused);
+        let suspended = spawn(&mut threads, 2);
+        threads.current_thread = Some(current);
+        threads.suspend(suspended);
+
+        for target in [current, other_prio, blocked, suspended, ThreadId::new(15)] {
+            assert!(!threads.yield_to(target));
+        }
+        assert_eq!(threads.runqueue.get_next(), Some(other_prio));
+        threads.runqueue.del(other_prio, RunqueueId::new(3));
+        assert_eq!(drain_runqueue(&mut threads, RunqueueId::new(2)), [current]);
+    }
+
+    #[test]
+    fn test_suspend_running() {
+        let mut threads = Threads::new();
+        let pid = spawn(&mut threads, 2);
+
+        assert!(threads.suspend(pid));
+        assert!(!threads.suspend(pid));
+        assert!(!threads.runqueue.is_queued(pid));
+
+        assert!(threads.resume(pid));
+        assert!(!threads.resume(pid));
+        assert!(threads.runqueue.is_queued(pid));
+        assert!(threads.runqueue.is_consistent());
+    }
+
+    #[test]
+    fn test_suspend_blocked() {
+        let mut threads = Threads::new();
+        let pid = spawn(&mut threads, 2);
+        threads.set_state(
+            pid,
+            ThreadState::FlagBlocked(thread_flags::WaitMode::Any(1)),
+        );
+
+        assert!(threads.suspend(pid));
+        // Becoming ready to run doesn't make it runnable.
+        threads.set_state(pid, ThreadState::Running);
+        assert!(!threads.runqueue.is_queued(pid));
+
+        assert!(threads.resume(pid));
+        assert!(threads.runqueue.is_queued(pid));
+        assert!(threads.runqueue.is_consistent());
+    }
+
+    #[test]
+    fn test_suspend_creating() {
+        let mut threads = Threads::new();
+        let pid = threads.reserve(RunqueueId::new(2)).unwrap();
+
+        assert!(threads.suspend(pid));
+        // Publishing the thread, as `thread_create_raw()` does.
+        threads.set_state(pid, ThreadState::Running);
+        assert!(!threads.runqueue.is_queued(pid));
+
+        assert!(threads.resume(pid));
+        assert!(threads.runqueue.is_queued(pid));
+        assert!(threads.runqueue.is_consistent());
+    }
+
+    #[test]
+    fn test_suspend_invalid() {
+        let mut threads = Threads::new();
+        assert!(!threads.suspend(ThreadId::new(0)));
+        assert!(!threads.resume(ThreadId::new(0)));
+    }
+
+    #[test]
+    fn test_boosted_priority_nesting() {
+        let mut threads = Threads::new();
+        let current = spawn(&mut threads, 2);
+        threads.current_thread = Some(current);
+
+        let outer = threads.boost_current(RunqueueId::new(5));
+        // A lower inner boost has no effect.
+        let inner = threads.boost_current(RunqueueId::new(3));
+        assert_eq!(threads.current().unwrap().prio, RunqueueId::new(5));
+        threads.restore_current_priority(inner);
+        assert_eq!(threads.current().unwrap().prio, RunqueueId::new(5));
+        threads.restore_current_priority(outer);
+        assert_eq!(threads.current().unwrap().prio, RunqueueId::new(2));
+    }
 }
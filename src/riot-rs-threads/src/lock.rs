@@ -2,6 +2,8 @@
 use core::cell::UnsafeCell;
 
 use super::threadlist::ThreadList;
+#[cfg(feature = "lock-owner")]
+use super::ThreadId;
 use super::ThreadState;
 
 /// A basic locking object.
@@ -10,6 +12,9 @@ use super::ThreadState;
 /// This is supposed to be used to implement other locking primitives.
 pub struct Lock {
     state: UnsafeCell<LockState>,
+    /// The thread currently holding the lock, if known.
+    #[cfg(feature = "lock-owner")]
+    owner: UnsafeCell<Option<ThreadId>>,
 }
 
 unsafe impl Sync for Lock {}
@@ -24,6 +29,8 @@ impl Lock {
     pub const fn new() -> Self {
         Self {
             state: UnsafeCell::new(LockState::Unlocked),
+            #[cfg(feature = "lock-owner")]
+            owner: UnsafeCell::new(None),
         }
     }
 
@@ -31,6 +38,8 @@ impl Lock {
     pub const fn new_locked() -> Self {
         Self {
             state: UnsafeCell::new(LockState::Locked(ThreadList::new())),
+            #[cfg(feature = "lock-owner")]
+            owner: UnsafeCell::new(None),
         }
     }
 
@@ -55,7 +64,11 @@ impl Lock {
         critical_section::with(|cs| {
             let state = unsafe { &mut *self.state.get() };
             match state {
-                LockState::Unlocked => *state = LockState::Locked(ThreadList::new()),
+                LockState::Unlocked => {
+                    *state = LockState::Locked(ThreadList::new());
+                    #[cfg(feature = "lock-owner")]
+                    self.set_owner(crate::current_pid());
+                }
                 LockState::Locked(waiters) => {
                     waiters.put_current(cs, ThreadState::LockBlocked);
                 }
@@ -73,6 +86,8 @@ impl Lock {
             match state {
                 LockState::Unlocked => {
                     *state = LockState::Locked(ThreadList::new());
+                    #[cfg(feature = "lock-owner")]
+                    self.set_owner(crate::current_pid());
                     true
                 }
                 LockState::Locked(_) => false,
@@ -92,13 +107,35 @@ impl Lock {
             match state {
                 LockState::Unlocked => {}
                 LockState::Locked(waiters) => {
-                    if waiters.pop(cs).is_none() {
+                    let next_owner = waiters.pop(cs).map(|(thread_id, _)| thread_id);
+                    if next_owner.is_none() {
                         *state = LockState::Unlocked
                     }
+                    #[cfg(feature = "lock-owner")]
+                    self.set_owner(next_owner);
                 }
             }
         })
     }
+
+    /// Returns the thread currently holding the lock.
+    ///
+    /// Returns `None` if the lock is unlocked, or if it was created locked and
+    /// hasn't been released since.
+    ///
+    /// Meant for debugging, e.g., to find out which thread keeps a lock.
+    #[cfg(feature = "lock-owner")]
+    pub fn owner(&self) -> Option<ThreadId> {
+        // SAFETY: only accessed from within critical sections.
+        critical_section::with(|_| unsafe { *self.owner.get() })
+    }
+
+    /// Must be called from within a critical section.
+    #[cfg(feature = "lock-owner")]
+    fn set_owner(&self, owner: Option<ThreadId>) {
+        // SAFETY: only accessed from within critical sections.
+        unsafe { *self.owner.get() = owner };
+    }
 }
 
 impl Default for Lock {
@@ -0,0 +1,36 @@
+//! Named thread priority bands.
+//!
+//! Subsystems and applications should pick their thread priorities from these bands instead of
+//! using bare numbers, so that their relative order is defined in a single place. A band's value
+//! may be offset by a few levels, as long as the result stays below the next band.
+//!
+//! Higher values mean higher priority.
+
+use crate::SCHED_PRIO_LEVELS;
+
+/// The lowest priority, for background work that should only run when nothing else does.
+pub const LOWEST: u8 = 0;
+
+/// The default priority of application threads.
+pub const APP: u8 = 1;
+
+/// Application threads that should preempt regular application threads.
+pub const APP_HIGH: u8 = 4;
+
+/// Threads handling sensors and other peripherals.
+pub const SENSOR: u8 = 6;
+
+/// Threads of the network stack and radio drivers.
+pub const NETWORK: u8 = 8;
+
+/// The highest priority.
+pub const HIGHEST: u8 = (SCHED_PRIO_LEVELS - 1) as u8;
+
+const _: () = {
+    assert!(SCHED_PRIO_LEVELS <= u8::MAX as usize + 1);
+    assert!(LOWEST < APP);
+    assert!(APP < APP_HIGH);
+    assert!(APP_HIGH < SENSOR);
+    assert!(SENSOR < NETWORK);
+    assert!(NETWORK < HIGHEST);
+};
@@ -0,0 +1,91 @@
+//! Context switch latency measurement.
+//!
+//! With the `sched-latency` feature enabled, the scheduler records the time from a scheduler
+//! trigger to the switch to the next thread, in CPU cycles, into a histogram. This allows ports to
+//! document and track their context switch latency.
+//!
+//! The switch is timestamped when the scheduler has picked the next thread, right before its
+//! registers are restored. Triggers that don't result in a switch are not recorded, nor are
+//! triggers before threading started, as the cycle counter isn't running yet.
+//!
+//! Only supported on Cortex-M3 and higher, which provide a cycle counter.
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// Number of histogram buckets, see [`histogram()`].
+pub const BUCKETS: usize = 16;
+
+static HISTOGRAM: [AtomicU32; BUCKETS] = [const { AtomicU32::new(0) }; BUCKETS];
+
+/// Cycle count of the earliest trigger not followed by a scheduler invocation yet.
+static TRIGGERED_AT: AtomicU32 = AtomicU32::new(0);
+static TRIGGERED: AtomicBool = AtomicBool::new(false);
+/// Whether the cycle counter is running, see [`start()`].
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Returns the latency histogram.
+///
+/// Bucket `0` counts switches that took no cycles, bucket `i` those that took
+/// `2^(i-1)..2^i` cycles. The last bucket also counts all longer ones.
+pub fn histogram() -> [u32; BUCKETS] {
+    core::array::from_fn(|i| HISTOGRAM[i].load(Ordering::Relaxed))
+}
+
+/// Clears the latency histogram.
+pub fn reset() {
+    for bucket in &HISTOGRAM {
+        bucket.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Enables recording, once the cycle counter is running.
+#[cfg_attr(not(context = "riot-rs"), allow(dead_code))]
+pub(crate) fn start() {
+    STARTED.store(true, Ordering::Relaxed);
+}
+
+/// Records a scheduler trigger at cycle count `now`.
+///
+/// Only the first trigger before the scheduler runs counts. Triggers before [`start()`] are
+/// ignored.
+#[cfg_attr(not(context = "riot-rs"), allow(dead_code))]
+pub(crate) fn record_trigger(now: u32) {
+    critical_section::with(|_| {
+        if STARTED.load(Ordering::Relaxed) && !TRIGGERED.load(Ordering::Relaxed) {
+            TRIGGERED_AT.store(now, Ordering::Relaxed);
+            TRIGGERED.store(true, Ordering::Relaxed);
+        }
+    });
+}
+
+/// Records the scheduler's decision at cycle count `now`, and whether it switches threads.
+///
+/// Must be called from within a critical section.
+#[cfg_attr(not(context = "riot-rs"), allow(dead_code))]
+pub(crate) fn record_sched(now: u32, switched: bool) {
+    if !TRIGGERED.swap(false, Ordering::Relaxed) || !switched {
+        return;
+    }
+    let cycles = now.wrapping_sub(TRIGGERED_AT.load(Ordering::Relaxed));
+    let bucket = ((u32::BITS - cycles.leading_zeros()) as usize).min(BUCKETS - 1);
+    HISTOGRAM[bucket].fetch_add(1, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_triggers_before_start_ignored() {
+        record_trigger(100);
+        record_sched(110, true);
+        assert_eq!(histogram(), [0; BUCKETS]);
+
+        start();
+        record_trigger(100);
+        record_sched(110, true);
+        // 10 cycles fall into the `8..16` bucket.
+        let mut expected = [0; BUCKETS];
+        expected[4] = 1;
+        assert_eq!(histogram(), expected);
+    }
+}
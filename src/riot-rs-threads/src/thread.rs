@@ -10,14 +10,25 @@ pub struct Thread {
     /// Priority of the thread between 0..[`super::SCHED_PRIO_LEVELS`].
     /// Multiple threads may have the same priority.
     pub prio: RunqueueId,
+    /// Priority of the thread without boosts by threads waiting for it in
+    /// [`super::thread_flags::wait_any_from()`].
+    pub base_prio: RunqueueId,
     /// Id of the thread between 0..[`super::THREADS_NUMOF`].
     /// Ids are unique while a thread is alive but reused after a thread finished.
     pub pid: ThreadId,
     /// Flags set for the thread.
     pub flags: ThreadFlags,
+    /// Whether the thread was suspended by [`super::suspend()`].
+    ///
+    /// A suspended thread is not in the runqueue, even if it is
+    /// [`ThreadState::Running`].
+    pub suspended: bool,
     /// Arch-specific thread data.
     #[allow(dead_code)]
     pub(crate) data: ThreadData,
+    /// Address of the stack canary, at the bottom of the thread's stack.
+    #[cfg(feature = "stack-canary")]
+    pub(crate) canary: usize,
 }
 
 /// Possible states of a thread
@@ -32,6 +43,11 @@ pub enum ThreadState {
     Running,
     /// Suspended / paused.
     Paused,
+    /// Reserved for a thread that is being created.
+    ///
+    /// The thread's stack isn't set up yet, so unlike [`ThreadState::Paused`],
+    /// nothing but [`super::thread_create_raw()`] makes it runnable.
+    Creating,
     /// Waiting to acquire a [`super::lock::Lock`].
     LockBlocked,
     /// Waiting for [`ThreadFlags`] to be set.
@@ -40,6 +56,21 @@ pub enum ThreadState {
     ChannelRxBlocked(usize),
     /// Waiting to send on a [`super::channel::Channel`], i.e. waiting for the receiver.
     ChannelTxBlocked(usize),
+    /// Waiting for the value of a [`super::watch::Watch`] to change.
+    WatchBlocked,
+}
+
+impl ThreadState {
+    /// Whether a thread in this state waits in a [`super::threadlist::ThreadList`].
+    pub(crate) fn is_list_blocked(self) -> bool {
+        matches!(
+            self,
+            ThreadState::LockBlocked
+                | ThreadState::ChannelRxBlocked(_)
+                | ThreadState::ChannelTxBlocked(_)
+                | ThreadState::WatchBlocked
+        )
+    }
 }
 
 impl Thread {
@@ -50,8 +81,12 @@ impl Thread {
             state: ThreadState::Invalid,
             data: Cpu::DEFAULT_THREAD_DATA,
             flags: 0,
+            suspended: false,
             prio: RunqueueId::new(0),
+            base_prio: RunqueueId::new(0),
             pid: ThreadId::new(0),
+            #[cfg(feature = "stack-canary")]
+            canary: 0,
         }
     }
 }
@@ -53,6 +53,43 @@ pub fn wait_any(mask: ThreadFlags) -> ThreadFlags {
     }
 }
 
+/// Waits until any flag in `mask` is set for the current thread, by `setter`.
+///
+/// Same as [`wait_any`], but while waiting, `setter` runs with at least the
+/// current thread's priority. This avoids a medium priority thread delaying a
+/// high priority thread that waits for a low priority producer.
+///
+/// Several threads may boost the same `setter`: it keeps the highest of their
+/// priorities until the last of them returns, then gets its own priority back.
+///
+/// A `setter` that is blocked in a wait list, e.g. on a lock or channel, is not
+/// boosted, as it would otherwise be out of order in that list. If `setter`
+/// blocks on a wait list while boosted, its priority is only lowered as far as
+/// that list's order allows.
+///
+/// Returns all set flags for this mask and clears them for the thread.
+///
+/// # Panics
+///
+/// Panics if this is called outside of a thread context.
+pub fn wait_any_from(setter: ThreadId, mask: ThreadFlags) -> ThreadFlags {
+    THREADS.with_mut(|mut threads| {
+        let waiter = threads.current_pid().unwrap();
+        threads.boost_setter(waiter, setter);
+    });
+
+    let flags = wait_any(mask);
+
+    THREADS.with_mut(|mut threads| {
+        let waiter = threads.current_pid().unwrap();
+        if threads.unboost_setter(waiter) {
+            crate::schedule();
+        }
+    });
+
+    flags
+}
+
 /// Waits until any flag in `mask` is set for the current thread.
 ///
 /// Compared to [`wait_any`], this returns and clears only one flag
@@ -94,6 +131,34 @@ pub fn get() -> ThreadFlags {
 }
 
 impl Threads {
+    /// Boosts `setter` to at least the priority of `waiter` until
+    /// [`Threads::unboost_setter()`], see [`wait_any_from()`].
+    ///
+    /// Returns `false`, without boosting, if `setter` does not exist, is
+    /// `waiter` itself or is blocked in a wait list.
+    fn boost_setter(&mut self, waiter: ThreadId, setter: ThreadId) -> bool {
+        if !self.is_valid_pid(setter)
+            || setter == waiter
+            || self.get_unchecked_mut(setter).state.is_list_blocked()
+        {
+            return false;
+        }
+        self.flag_boosts[usize::from(waiter)] = Some(setter);
+        self.update_priority(setter);
+        true
+    }
+
+    /// Ends the boost applied by `waiter`, if any.
+    ///
+    /// Returns `false` if `waiter` wasn't boosting any thread.
+    fn unboost_setter(&mut self, waiter: ThreadId) -> bool {
+        let Some(setter) = self.flag_boosts[usize::from(waiter)].take() else {
+            return false;
+        };
+        self.update_priority(setter);
+        true
+    }
+
     // thread flags implementation
     fn flag_set(&mut self, thread_id: ThreadId, mask: ThreadFlags) {
         let thread = self.get_unchecked_mut(thread_id);
@@ -153,3 +218,59 @@ impl Threads {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::spawn;
+    use crate::RunqueueId;
+
+    #[test]
+    fn test_overlapping_boosts() {
+        // Restore in the order of boosting, and in reverse.
+        for reverse in [false, true] {
+            let mut threads = Threads::new();
+            let setter = spawn(&mut threads, 1);
+            let first = spawn(&mut threads, 5);
+            let second = spawn(&mut threads, 7);
+
+            assert!(threads.boost_setter(first, setter));
+            assert_eq!(threads.get_unchecked_mut(setter).prio, RunqueueId::new(5));
+            assert!(threads.boost_setter(second, setter));
+            assert_eq!(threads.get_unchecked_mut(setter).prio, RunqueueId::new(7));
+
+            let (restored_first, restored_last, remaining_prio) = if reverse {
+                (second, first, 5)
+            } else {
+                (first, second, 7)
+            };
+            assert!(threads.unboost_setter(restored_first));
+            assert_eq!(
+                threads.get_unchecked_mut(setter).prio,
+                RunqueueId::new(remaining_prio)
+            );
+            assert!(threads.unboost_setter(restored_last));
+            assert_eq!(threads.get_unchecked_mut(setter).prio, RunqueueId::new(1));
+            assert!(!threads.unboost_setter(restored_last));
+
+            assert!(threads.runqueue.is_queued(setter));
+            assert!(threads.runqueue.is_consistent());
+        }
+    }
+
+    #[test]
+    fn test_boost_keeps_base_priority_changes() {
+        let mut threads = Threads::new();
+        let setter = spawn(&mut threads, 1);
+        let waiter = spawn(&mut threads, 5);
+
+        threads.boost_setter(waiter, setter);
+        // E.g., `with_boosted_priority()` called by the setter while boosted.
+        threads.get_unchecked_mut(setter).base_prio = RunqueueId::new(8);
+        threads.update_priority(setter);
+        assert_eq!(threads.get_unchecked_mut(setter).prio, RunqueueId::new(8));
+
+        threads.unboost_setter(waiter);
+        assert_eq!(threads.get_unchecked_mut(setter).prio, RunqueueId::new(8));
+    }
+}
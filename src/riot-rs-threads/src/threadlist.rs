@@ -1,8 +1,11 @@
 use critical_section::CriticalSection;
 
-use crate::{ThreadId, ThreadState, THREADS};
+use crate::{ThreadId, ThreadState, Threads, THREADS};
 
 /// Manages blocked [`super::Thread`]s for a resource, and triggering the scheduler when needed.
+///
+/// Threads are ordered by priority, highest first. Threads of the same priority are kept in
+/// FIFO order.
 #[derive(Debug, Default)]
 pub struct ThreadList {
     /// Next thread to run once the resource is available.
@@ -16,16 +19,38 @@ impl ThreadList {
     }
 
     /// Puts the current (blocked) thread into this [`ThreadList`] and triggers the scheduler.
+    ///
+    /// The thread is inserted behind all threads with the same or a higher priority.
     pub fn put_current(&mut self, cs: CriticalSection, state: ThreadState) {
         THREADS.with_mut_cs(cs, |mut threads| {
             let thread_id = threads.current_thread.unwrap();
-            threads.thread_blocklist[usize::from(thread_id)] = self.head;
-            self.head = Some(thread_id);
+            self.insert(&mut threads, thread_id);
             threads.set_state(thread_id, state);
             crate::schedule();
         });
     }
 
+    /// Inserts `thread_id` behind all threads with the same or a higher priority.
+    fn insert(&mut self, threads: &mut Threads, thread_id: ThreadId) {
+        let prio = threads.get_unchecked_mut(thread_id).prio;
+
+        let mut prev = None;
+        let mut next = self.head;
+        while let Some(n) = next {
+            if threads.get_unchecked_mut(n).prio < prio {
+                break;
+            }
+            prev = Some(n);
+            next = threads.thread_blocklist[usize::from(n)];
+        }
+
+        threads.thread_blocklist[usize::from(thread_id)] = next;
+        match prev {
+            Some(prev) => threads.thread_blocklist[usize::from(prev)] = Some(thread_id),
+            None => self.head = Some(thread_id),
+        }
+    }
+
     /// Removes the head from this [`ThreadList`].
     ///
     /// Sets the thread's [`ThreadState`] to [`ThreadState::Running`] and triggers
@@ -33,21 +58,54 @@ impl ThreadList {
     ///
     /// Returns the thread's [`ThreadId`] and its previous [`ThreadState`].
     pub fn pop(&mut self, cs: CriticalSection) -> Option<(ThreadId, ThreadState)> {
-        if let Some(head) = self.head {
-            let old_state = THREADS.with_mut_cs(cs, |mut threads| {
-                self.head = threads.thread_blocklist[usize::from(head)].take();
+        if self.head.is_some() {
+            THREADS.with_mut_cs(cs, |mut threads| {
+                let head = self.take_head(&mut threads)?;
                 let old_state = threads.set_state(head, ThreadState::Running);
                 crate::schedule();
-                old_state
-            });
-            Some((head, old_state))
+                Some((head, old_state))
+            })
         } else {
             None
         }
     }
 
+    /// Unlinks the head from this [`ThreadList`], leaving its state untouched.
+    fn take_head(&mut self, threads: &mut Threads) -> Option<ThreadId> {
+        let head = self.head?;
+        self.head = threads.thread_blocklist[usize::from(head)].take();
+        Some(head)
+    }
+
     /// Determines if this [`ThreadList`] is empty.
     pub fn is_empty(&self, _cs: CriticalSection) -> bool {
         self.head.is_none()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::spawn;
+
+    #[test]
+    fn test_priority_order() {
+        let mut threads = Threads::new();
+        let mut list = ThreadList::new();
+
+        let low = spawn(&mut threads, 1);
+        let high_first = spawn(&mut threads, 3);
+        let mid = spawn(&mut threads, 2);
+        let high_second = spawn(&mut threads, 3);
+        for pid in [low, high_first, mid, high_second] {
+            list.insert(&mut threads, pid);
+        }
+
+        // Highest priority first, FIFO within the same priority.
+        for pid in [high_first, high_second, mid, low] {
+            assert_eq!(list.take_head(&mut threads), Some(pid));
+        }
+        assert_eq!(list.take_head(&mut threads), None);
+        assert!(threads.thread_blocklist.iter().all(Option::is_none));
+    }
+}
@@ -0,0 +1,244 @@
+//! Single-value, many-observers synchronization primitive.
+//!
+//! A [`Watch`] holds the latest value of something, e.g., a connectivity state or a sensor
+//! reading. Observers are notified of changes, both from threads (blocking) and from async tasks
+//! (awaiting). Observers only ever see the latest value: intermediate values sent while an
+//! observer did not look are lost.
+use core::cell::UnsafeCell;
+use core::future::poll_fn;
+use core::task::{Poll, Waker};
+
+use crate::threadlist::ThreadList;
+use crate::ThreadState;
+
+/// Holds a value of type `T` that can be observed for changes.
+///
+/// Up to `WAKERS` async tasks can wait for changes concurrently. If more do, all of them are woken
+/// and re-register, which costs spurious polls but keeps working. There is no limit on the
+/// number of waiting threads.
+pub struct Watch<T: Copy + Send, const WAKERS: usize = 4> {
+    inner: UnsafeCell<Inner<T, WAKERS>>,
+}
+
+unsafe impl<T: Copy + Send, const WAKERS: usize> Sync for Watch<T, WAKERS> {}
+
+struct Inner<T, const WAKERS: usize> {
+    value: T,
+    /// Incremented on every [`Watch::send()`].
+    version: u32,
+    waiters: ThreadList,
+    wakers: [Option<Waker>; WAKERS],
+}
+
+impl<T: Copy + Send, const WAKERS: usize> Watch<T, WAKERS> {
+    /// Creates a new [`Watch`] holding `value`.
+    pub const fn new(value: T) -> Self {
+        Self {
+            inner: UnsafeCell::new(Inner {
+                value,
+                version: 0,
+                waiters: ThreadList::new(),
+                wakers: [const { None }; WAKERS],
+            }),
+        }
+    }
+
+    /// Replaces the value and notifies all observers waiting for a change.
+    ///
+    /// This may be called from threads, ISRs and async tasks.
+    pub fn send(&self, value: T) {
+        critical_section::with(|cs| {
+            let inner = unsafe { &mut *self.inner.get() };
+            inner.value = value;
+            inner.version = inner.version.wrapping_add(1);
+            while inner.waiters.pop(cs).is_some() {}
+            for waker in inner.wakers.iter_mut().filter_map(Option::take) {
+                waker.wake();
+            }
+        });
+    }
+
+    /// Returns the current value.
+    pub fn get(&self) -> T {
+        critical_section::with(|_| unsafe { &*self.inner.get() }.value)
+    }
+
+    /// Returns a [`Receiver`] to observe changes, starting with the current value considered
+    /// seen.
+    pub fn receiver(&self) -> Receiver<'_, T, WAKERS> {
+        let version = critical_section::with(|_| unsafe { &*self.inner.get() }.version);
+        Receiver {
+            watch: self,
+            version,
+        }
+    }
+}
+
+/// Observes a [`Watch`], keeping track of the last value seen.
+pub struct Receiver<'a, T: Copy + Send, const WAKERS: usize = 4> {
+    watch: &'a Watch<T, WAKERS>,
+    version: u32,
+}
+
+impl<'a, T: Copy + Send, const WAKERS: usize> Receiver<'a, T, WAKERS> {
+    /// Returns the value if it changed since it was last seen, and marks it as seen.
+    pub fn try_changed(&mut self) -> Option<T> {
+        critical_section::with(|_| {
+            let inner = unsafe { &*self.watch.inner.get() };
+            self.take_if_changed(inner)
+        })
+    }
+
+    /// Blocks until the value changed since it was last seen, then returns it and marks it as
+    /// seen.
+    ///
+    /// **NOTE**: must not be called outside thread context!
+    pub fn changed(&mut self) -> T {
+        loop {
+            let value = critical_section::with(|cs| {
+                let inner = unsafe { &mut *self.watch.inner.get() };
+                let value = self.take_if_changed(inner);
+                if value.is_none() {
+                    inner.waiters.put_current(cs, ThreadState::WatchBlocked);
+                }
+                value
+            });
+            // If the thread had to block, it was woken up by `send()`, so the value has changed
+            // unless a receiver of the same thread consumed it in the meantime.
+            if let Some(value) = value {
+                return value;
+            }
+        }
+    }
+
+    /// Waits until the value changed since it was last seen, then returns it and marks it as
+    /// seen.
+    pub async fn changed_async(&mut self) -> T {
+        poll_fn(|cx| {
+            critical_section::with(|_| {
+                let inner = unsafe { &mut *self.watch.inner.get() };
+                if let Some(value) = self.take_if_changed(inner) {
+                    return Poll::Ready(value);
+                }
+                register_waker(&mut inner.wakers, cx.waker());
+                Poll::Pending
+            })
+        })
+        .await
+    }
+
+    fn take_if_changed(&mut self, inner: &Inner<T, WAKERS>) -> Option<T> {
+        if inner.version == self.version {
+            return None;
+        }
+        self.version = inner.version;
+        Some(inner.value)
+    }
+}
+
+/// Registers `waker` in a free slot of `wakers`, unless it is already registered.
+///
+/// If all slots are taken, wakes and clears all of them to make room.
+fn register_waker(wakers: &mut [Option<Waker>], waker: &Waker) {
+    if wakers
+        .iter()
+        .flatten()
+        .any(|registered| registered.will_wake(waker))
+    {
+        return;
+    }
+    if wakers.iter().all(Option::is_some) {
+        for registered in wakers.iter_mut().filter_map(Option::take) {
+            registered.wake();
+        }
+    }
+    if let Some(slot) = wakers.iter_mut().find(|slot| slot.is_none()) {
+        *slot = Some(waker.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use core::future::Future;
+    use core::pin::pin;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use core::task::{Context, Poll};
+    use std::sync::Arc;
+    use std::task::Wake;
+
+    use super::*;
+
+    /// Counts how often it was woken.
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_try_changed() {
+        let watch: Watch<u32> = Watch::new(1);
+        let mut receiver = watch.receiver();
+        // The value at subscription time counts as seen.
+        assert_eq!(receiver.try_changed(), None);
+
+        watch.send(2);
+        watch.send(3);
+        // Only the latest value is observed, once.
+        assert_eq!(receiver.try_changed(), Some(3));
+        assert_eq!(receiver.try_changed(), None);
+        assert_eq!(watch.get(), 3);
+    }
+
+    #[test]
+    fn test_changed_does_not_block_when_changed() {
+        let watch: Watch<u32> = Watch::new(1);
+        let mut receiver = watch.receiver();
+
+        watch.send(2);
+        assert_eq!(receiver.changed(), 2);
+    }
+
+    #[test]
+    fn test_changed_async() {
+        let watch: Watch<u32, 2> = Watch::new(1);
+        let mut receiver = watch.receiver();
+
+        let waker = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let std_waker = Waker::from(waker.clone());
+        let mut cx = Context::from_waker(&std_waker);
+
+        let mut changed = pin!(receiver.changed_async());
+        assert_eq!(changed.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(waker.0.load(Ordering::Relaxed), 0);
+
+        watch.send(2);
+        assert_eq!(waker.0.load(Ordering::Relaxed), 1);
+        assert_eq!(changed.as_mut().poll(&mut cx), Poll::Ready(2));
+    }
+
+    #[test]
+    fn test_register_waker_overflow() {
+        let wakers: [_; 3] = core::array::from_fn(|_| Arc::new(CountingWaker(AtomicUsize::new(0))));
+        let mut slots: [Option<Waker>; 2] = [None, None];
+
+        register_waker(&mut slots, &Waker::from(wakers[0].clone()));
+        register_waker(&mut slots, &Waker::from(wakers[1].clone()));
+        // Registering an already registered waker takes no slot.
+        register_waker(&mut slots, &Waker::from(wakers[1].clone()));
+        assert_eq!(wakers[0].0.load(Ordering::Relaxed), 0);
+
+        // All slots are taken: the registered wakers are woken to make room.
+        register_waker(&mut slots, &Waker::from(wakers[2].clone()));
+        assert_eq!(wakers[0].0.load(Ordering::Relaxed), 1);
+        assert_eq!(wakers[1].0.load(Ordering::Relaxed), 1);
+        assert!(slots[0]
+            .as_ref()
+            .unwrap()
+            .will_wake(&Waker::from(wakers[2].clone())));
+    }
+}
@@ -1,2 +1,4 @@
-#![no_std]
+#![cfg_attr(not(test), no_std)]
 pub mod env;
+pub mod os_cell;
+pub mod pool;
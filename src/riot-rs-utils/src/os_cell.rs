@@ -0,0 +1,108 @@
+//! Global singletons initialized once during system initialization.
+//!
+//! This module provides [`OsCell`], a cell meant for statics that are set once, typically from an
+//! init function, and only read afterwards.
+
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// A cell that can be set once, and read without locking afterwards.
+///
+/// Setting the value takes a critical section, reading it only an atomic load, so this also works
+/// on targets without atomic compare-and-swap.
+pub struct OsCell<T> {
+    initialized: AtomicBool,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+// SAFETY: the value is only written once, before `initialized` is set, and only shared afterwards.
+unsafe impl<T: Send + Sync> Sync for OsCell<T> {}
+
+impl<T> OsCell<T> {
+    /// Creates a new, uninitialized [`OsCell`].
+    pub const fn new() -> Self {
+        Self {
+            initialized: AtomicBool::new(false),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Initializes the cell with `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `value` back if the cell is already initialized.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        critical_section::with(|_| {
+            if self.initialized.load(Ordering::Relaxed) {
+                return Err(value);
+            }
+            // SAFETY: the cell is not initialized, so there are no references to the value, and
+            // the critical section prevents concurrent initialization.
+            unsafe { (*self.value.get()).write(value) };
+            self.initialized.store(true, Ordering::Release);
+            Ok(())
+        })
+    }
+
+    /// Returns the value, or `None` if the cell is not initialized yet.
+    pub fn get(&self) -> Option<&T> {
+        if self.initialized.load(Ordering::Acquire) {
+            // SAFETY: the value is initialized and never written again.
+            Some(unsafe { (*self.value.get()).assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cell is not initialized yet, i.e., if it is accessed before the system
+    /// initialization step that sets it.
+    #[track_caller]
+    pub fn get_initialized(&self) -> &T {
+        self.get()
+            .expect("OsCell accessed before it was initialized")
+    }
+}
+
+impl<T> Default for OsCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for OsCell<T> {
+    fn drop(&mut self) {
+        if *self.initialized.get_mut() {
+            // SAFETY: the value is initialized and not referenced anymore.
+            unsafe { self.value.get_mut().assume_init_drop() };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_once() {
+        let cell = OsCell::new();
+        assert_eq!(cell.get(), None);
+
+        assert_eq!(cell.set(1), Ok(()));
+        assert_eq!(cell.set(2), Err(2));
+        assert_eq!(cell.get(), Some(&1));
+        assert_eq!(*cell.get_initialized(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "OsCell accessed before it was initialized")]
+    fn test_get_initialized_uninitialized() {
+        let cell: OsCell<u32> = OsCell::new();
+        cell.get_initialized();
+    }
+}
@@ -0,0 +1,231 @@
+//! Fixed-size object pools.
+//!
+//! A [`Pool`] statically reserves memory for `N` objects of type `T` and hands out slots as
+//! [`PoolBox`]es, which return their slot to the pool when dropped. This replaces ad-hoc static
+//! arrays with "in use" flags, e.g., for packet descriptors or per-connection state.
+
+// Slot indices are always positions within arrays of length `N`.
+#![allow(clippy::indexing_slicing)]
+
+use core::cell::{Cell, UnsafeCell};
+use core::mem::MaybeUninit;
+use core::ops::{Deref, DerefMut};
+
+use critical_section::Mutex;
+
+/// A pool of up to `N` objects of type `T`.
+///
+/// Allocation and deallocation are O(`N`) and O(1) respectively, and can be done from any
+/// context, including interrupt handlers.
+pub struct Pool<T, const N: usize> {
+    slots: [UnsafeCell<MaybeUninit<T>>; N],
+    state: Mutex<State<N>>,
+}
+
+// SAFETY: each slot is only accessed through the single `PoolBox` it has been handed out as.
+unsafe impl<T: Send, const N: usize> Sync for Pool<T, N> {}
+
+struct State<const N: usize> {
+    used: [Cell<bool>; N],
+    in_use: Cell<usize>,
+    high_water: Cell<usize>,
+    failures: Cell<usize>,
+}
+
+/// Usage statistics of a [`Pool`], see [`Pool::stats()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Total number of slots.
+    pub capacity: usize,
+    /// Number of slots currently allocated.
+    pub in_use: usize,
+    /// Highest number of slots allocated at the same time.
+    pub high_water: usize,
+    /// Number of allocations that failed because the pool was exhausted.
+    pub failures: usize,
+}
+
+impl<T, const N: usize> Pool<T, N> {
+    /// Creates a new, empty [`Pool`].
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            state: Mutex::new(State {
+                used: [const { Cell::new(false) }; N],
+                in_use: Cell::new(0),
+                high_water: Cell::new(0),
+                failures: Cell::new(0),
+            }),
+        }
+    }
+
+    /// Moves `value` into a free slot.
+    ///
+    /// # Errors
+    ///
+    /// Returns `value` back if all slots are in use.
+    pub fn try_alloc(&self, value: T) -> Result<PoolBox<'_, T, N>, T> {
+        let index = critical_section::with(|cs| {
+            let state = self.state.borrow(cs);
+            let Some(index) = state.used.iter().position(|used| !used.get()) else {
+                state.failures.set(state.failures.get().saturating_add(1));
+                return None;
+            };
+            state.used[index].set(true);
+            let in_use = state.in_use.get() + 1;
+            state.in_use.set(in_use);
+            state.high_water.set(state.high_water.get().max(in_use));
+            Some(index)
+        });
+
+        match index {
+            Some(index) => {
+                // SAFETY: the slot was just marked as used, so nothing else accesses it.
+                unsafe { (*self.slots[index].get()).write(value) };
+                Ok(PoolBox { pool: self, index })
+            }
+            None => Err(value),
+        }
+    }
+
+    /// Returns the pool's usage statistics.
+    pub fn stats(&self) -> PoolStats {
+        critical_section::with(|cs| {
+            let state = self.state.borrow(cs);
+            PoolStats {
+                capacity: N,
+                in_use: state.in_use.get(),
+                high_water: state.high_water.get(),
+                failures: state.failures.get(),
+            }
+        })
+    }
+
+    fn free(&self, index: usize) {
+        critical_section::with(|cs| {
+            let state = self.state.borrow(cs);
+            state.used[index].set(false);
+            state.in_use.set(state.in_use.get() - 1);
+        });
+    }
+}
+
+impl<T, const N: usize> Default for Pool<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An object allocated in a [`Pool`].
+///
+/// The object is dropped and its slot freed when the [`PoolBox`] is dropped.
+pub struct PoolBox<'a, T, const N: usize> {
+    pool: &'a Pool<T, N>,
+    index: usize,
+}
+
+// SAFETY: a `PoolBox` owns its slot's content exclusively, like a `Box`.
+unsafe impl<'a, T: Send, const N: usize> Send for PoolBox<'a, T, N> {}
+unsafe impl<'a, T: Sync, const N: usize> Sync for PoolBox<'a, T, N> {}
+
+impl<'a, T, const N: usize> Deref for PoolBox<'a, T, N> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the slot is initialized and owned by this `PoolBox`.
+        unsafe { (*self.pool.slots[self.index].get()).assume_init_ref() }
+    }
+}
+
+impl<'a, T, const N: usize> DerefMut for PoolBox<'a, T, N> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the slot is initialized and owned by this `PoolBox`.
+        unsafe { (*self.pool.slots[self.index].get()).assume_init_mut() }
+    }
+}
+
+impl<'a, T, const N: usize> Drop for PoolBox<'a, T, N> {
+    fn drop(&mut self) {
+        // SAFETY: the slot is initialized and owned by this `PoolBox`, and freed right after.
+        unsafe { (*self.pool.slots[self.index].get()).assume_init_drop() };
+        self.pool.free(self.index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use super::*;
+
+    /// Counts its drops.
+    #[derive(Debug)]
+    struct DropCounter<'a>(&'a Cell<usize>);
+
+    impl Drop for DropCounter<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_exhaustion() {
+        let pool: Pool<u32, 2> = Pool::new();
+
+        let a = pool.try_alloc(1).unwrap();
+        let b = pool.try_alloc(2).unwrap();
+        assert!(matches!(pool.try_alloc(3), Err(3)));
+        assert_eq!((*a, *b), (1, 2));
+    }
+
+    #[test]
+    fn test_free_on_drop() {
+        let drops = Cell::new(0);
+        let pool: Pool<DropCounter, 1> = Pool::new();
+
+        let boxed = pool.try_alloc(DropCounter(&drops)).unwrap();
+        assert!(pool.try_alloc(DropCounter(&drops)).is_err());
+        // The rejected value was handed back and dropped.
+        assert_eq!(drops.get(), 1);
+
+        drop(boxed);
+        assert_eq!(drops.get(), 2);
+
+        // The slot can be reused.
+        let mut boxed = pool.try_alloc(DropCounter(&drops)).unwrap();
+        *boxed = DropCounter(&drops);
+        assert_eq!(drops.get(), 3);
+    }
+
+    #[test]
+    fn test_stats() {
+        let pool: Pool<u8, 3> = Pool::new();
+        assert_eq!(
+            pool.stats(),
+            PoolStats {
+                capacity: 3,
+                in_use: 0,
+                high_water: 0,
+                failures: 0,
+            }
+        );
+
+        let a = pool.try_alloc(0).unwrap();
+        let b = pool.try_alloc(0).unwrap();
+        drop(a);
+        let c = pool.try_alloc(0).unwrap();
+        let d = pool.try_alloc(0).unwrap();
+        assert!(pool.try_alloc(0).is_err());
+        drop((b, c, d));
+
+        assert_eq!(
+            pool.stats(),
+            PoolStats {
+                capacity: 3,
+                in_use: 0,
+                high_water: 3,
+                failures: 1,
+            }
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use std::env;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn git(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|output| output.trim().to_owned())
+}
+
+fn main() {
+    // Commit hash of the tree being built, if built from a git checkout, marked
+    // if tracked files have uncommitted changes.
+    let git_commit = match git(&["rev-parse", "--short=12", "HEAD"]) {
+        Some(commit) => {
+            let dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+                .is_some_and(|status| !status.is_empty());
+            if dirty {
+                format!("{commit}-dirty")
+            } else {
+                commit
+            }
+        }
+        None => "unknown".to_owned(),
+    };
+    println!("cargo:rustc-env=RIOT_RS_BUILD_GIT_COMMIT={git_commit}");
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        // `logs/HEAD` gets updated whenever HEAD moves, `index` whenever changes
+        // get staged or committed.
+        println!("cargo:rerun-if-changed={git_dir}/HEAD");
+        println!("cargo:rerun-if-changed={git_dir}/logs/HEAD");
+        println!("cargo:rerun-if-changed={git_dir}/index");
+    }
+
+    // Honor `SOURCE_DATE_EPOCH` to allow for reproducible builds.
+    // Otherwise, this is the time this script last ran: it is not rerun on
+    // every build, which would rebuild this crate every time.
+    let timestamp = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+        });
+    println!("cargo:rustc-env=RIOT_RS_BUILD_TIMESTAMP={timestamp}");
+
+    // Cargo passes enabled features as `CARGO_FEATURE_<NAME>`, upper-cased and
+    // with `-` replaced by `_`; turn them back into feature names.
+    // This is lossy: a feature named with `_` would be reported with `-`
+    // instead. The features of this crate are all kebab-case, so this only
+    // matters if that changes.
+    // `default` is skipped, the features it enables are listed on their own.
+    let mut features = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase().replace('_', "-"))
+        })
+        .filter(|feature| feature != "default")
+        .collect::<Vec<_>>();
+    features.sort();
+    println!(
+        "cargo:rustc-env=RIOT_RS_BUILD_FEATURES={}",
+        features.join(",")
+    );
+
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    println!("cargo:rerun-if-changed=build.rs");
+}
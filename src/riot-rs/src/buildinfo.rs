@@ -9,3 +9,48 @@ pub const BOARD: &str = riot_rs_utils::str_from_env_or!(
     "unknown",
     "board name provided by the build system"
 );
+
+/// The version of the `riot-rs` crate.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The abbreviated hash of the git commit RIOT-rs was built from.
+///
+/// `"unknown"` if RIOT-rs was not built from a git checkout. Suffixed with `-dirty` if tracked
+/// files had uncommitted changes.
+///
+/// Like [`BUILD_TIMESTAMP`], this reflects the checkout as of the last time the build script ran,
+/// i.e., the last time HEAD moved or changes were staged or committed. Unstaged edits alone don't
+/// update it.
+pub const GIT_COMMIT: &str = env!("RIOT_RS_BUILD_GIT_COMMIT");
+
+/// The time of the build, in seconds since the Unix epoch.
+///
+/// Taken from the `SOURCE_DATE_EPOCH` environment variable if set, which allows for reproducible
+/// builds. Otherwise, this is the time the `riot-rs` build script last ran, which is not
+/// necessarily the last build: the build script is only rerun if the git checkout, the build
+/// script itself or `SOURCE_DATE_EPOCH` changed.
+pub const BUILD_TIMESTAMP: u64 = {
+    let timestamp = env!("RIOT_RS_BUILD_TIMESTAMP");
+    match riot_rs_utils::env::konst::primitive::parse_u64(timestamp) {
+        Ok(timestamp) => timestamp,
+        Err(_) => riot_rs_utils::env::const_panic::concat_panic!(
+            "Could not parse `RIOT_RS_BUILD_TIMESTAMP=",
+            timestamp,
+            "` as a u64"
+        ),
+    }
+};
+
+/// The enabled Cargo features of the `riot-rs` crate, comma-separated.
+///
+/// As laze modules select functionality by enabling these features, this also reflects the laze
+/// modules selected for the build.
+///
+/// The names are reconstructed from what Cargo passes to the build script, which turns `-` into
+/// `_`; any `_` in a feature name thus shows up as `-`. `default` is not listed.
+pub const FEATURES: &str = env!("RIOT_RS_BUILD_FEATURES");
+
+/// Returns an iterator over the enabled Cargo features of the `riot-rs` crate.
+pub fn features() -> impl Iterator<Item = &'static str> {
+    FEATURES.split(',').filter(|feature| !feature.is_empty())
+}
@@ -29,9 +29,12 @@ pub use riot_rs_rt as rt;
 #[cfg(feature = "threading")]
 #[doc(inline)]
 pub use riot_rs_threads as thread;
+#[doc(inline)]
+pub use riot_rs_utils::os_cell;
 
 // Attribute macros
 pub use riot_rs_macros::config;
+pub use riot_rs_macros::ramfunc;
 pub use riot_rs_macros::spawner;
 pub use riot_rs_macros::task;
 #[cfg(any(feature = "threading", doc))]
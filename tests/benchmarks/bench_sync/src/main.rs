@@ -0,0 +1,117 @@
+#![no_main]
+#![no_std]
+#![feature(type_alias_impl_trait)]
+#![feature(used_with_arg)]
+
+use riot_rs::{
+    debug::println,
+    thread::{self, channel::Channel, flags, lock::Lock, ThreadId},
+};
+
+// Kept low enough for roundtrips not to overflow the 24-bit system timer.
+const ITERATIONS: usize = 1000;
+
+const FLAG: flags::ThreadFlags = 0b1;
+
+static LOCK: Lock = Lock::new();
+static PING: Channel<u32> = Channel::new();
+static PONG: Channel<u32> = Channel::new();
+static PEER: Channel<ThreadId> = Channel::new();
+static START: Channel<Phase> = Channel::new();
+static DONE: Channel<()> = Channel::new();
+
+/// Benchmarks the peer thread takes part in.
+#[derive(Clone, Copy)]
+enum Phase {
+    LockContended,
+    Channel,
+    Flags,
+}
+
+fn report(name: &str, result: Result<usize, riot_rs::bench::Error>) {
+    match result {
+        Ok(ticks) => println!("bench_sync,{},{}", name, ticks),
+        Err(err) => println!("bench_sync,{},error: {}", name, err),
+    }
+}
+
+/// Runs benchmark `name` while the peer serves `phase`.
+///
+/// Returns `false` if the benchmark failed. The peer may then be out of step, so no further phase
+/// must be started.
+fn run_with_peer(phase: Phase, name: &str, f: impl Fn()) -> bool {
+    START.send(&phase);
+    let result = riot_rs::bench::benchmark(ITERATIONS, f);
+    let ok = result.is_ok();
+    report(name, result);
+    if ok {
+        DONE.recv();
+    }
+    ok
+}
+
+#[riot_rs::thread(autostart)]
+fn thread0() {
+    let peer = PEER.recv();
+    PEER.send(&thread::current_pid().unwrap());
+
+    // The peer waits for a phase to start, so the lock is uncontended.
+    report(
+        "lock",
+        riot_rs::bench::benchmark(ITERATIONS, || {
+            LOCK.acquire();
+            LOCK.release();
+        }),
+    );
+
+    // Includes waiting for the peer to take the lock, then blocking on it until the peer
+    // hands it over.
+    let completed = run_with_peer(Phase::LockContended, "lock_contended", || {
+        flags::wait_any(FLAG);
+        LOCK.acquire();
+        LOCK.release();
+    }) && run_with_peer(Phase::Channel, "channel", || {
+        PING.send(&0);
+        PONG.recv();
+    }) && run_with_peer(Phase::Flags, "flags", || {
+        flags::set(peer, FLAG);
+        flags::wait_any(FLAG);
+    });
+
+    if !completed {
+        println!("bench_sync: skipped the remaining benchmarks");
+    }
+}
+
+#[riot_rs::thread(autostart)]
+fn thread1() {
+    PEER.send(&thread::current_pid().unwrap());
+    let peer = PEER.recv();
+
+    loop {
+        match START.recv() {
+            Phase::LockContended => {
+                for _ in 0..ITERATIONS {
+                    LOCK.acquire();
+                    flags::set(peer, FLAG);
+                    // Let the peer block on the lock before releasing it.
+                    thread::yield_same();
+                    LOCK.release();
+                }
+            }
+            Phase::Channel => {
+                for _ in 0..ITERATIONS {
+                    let value = PING.recv();
+                    PONG.send(&value);
+                }
+            }
+            Phase::Flags => {
+                for _ in 0..ITERATIONS {
+                    flags::wait_any(FLAG);
+                    flags::set(peer, FLAG);
+                }
+            }
+        }
+        DONE.send(&());
+    }
+}